@@ -1,64 +1,512 @@
 use ggez::audio::{self, SoundSource};
 use ggez::graphics::{self, Drawable};
-use ggez::mint::Point2;
-use ggez::{Context, GameResult};
-use std::fmt::Debug;
+use ggez::mint::{Point2, Vector2};
+use ggez::{Context, GameError, GameResult};
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::io::Read;
+
+// Muting only stops new sound effects from starting, anything already playing is left to finish.
+// Music keeps playing silently instead, so unmuting picks it up where it would have been.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioSettings {
+    pub master_volume: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        AudioSettings { master_volume: 1.0, muted: false }
+    }
+}
+
+impl AudioSettings {
+    pub fn toggle_mute(&mut self) {
+        self.muted = !self.muted;
+    }
+
+    // The volume a sound at `base_volume` plays at, silent when muted
+    pub fn volume(&self, base_volume: f32) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            base_volume * self.master_volume.clamp(0.0, 1.0)
+        }
+    }
+
+    // Calls `play` with the volume a sound should play at, unless muted
+    pub fn play(&self, base_volume: f32, play: impl FnOnce(f32)) {
+        if !self.muted {
+            play(self.volume(base_volume));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Skin {
+    Ferris,
+    Golden,
+    Ghost,
+}
+
+impl Skin {
+    pub const DEFAULT: Skin = Skin::Ferris;
+    pub const ALL: [Skin; 3] = [Skin::Ferris, Skin::Golden, Skin::Ghost];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Skin::Ferris => "Ferris",
+            Skin::Golden => "Golden",
+            Skin::Ghost  => "Ghost",
+        }
+    }
+
+    // Normal and shooting images
+    pub fn image_paths(self) -> (&'static str, &'static str) {
+        match self {
+            Skin::Ferris => ("/ferris-normal.png", "/ferris-shooting.png"),
+            Skin::Golden => ("/ferris-golden-normal.png", "/ferris-golden-shooting.png"),
+            Skin::Ghost  => ("/ferris-ghost-normal.png", "/ferris-ghost-shooting.png"),
+        }
+    }
+
+    // Wraps around at either end
+    pub fn next(self) -> Skin {
+        let index = Self::ALL.iter().position(|&skin| skin == self).unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Skin {
+        let index = Self::ALL.iter().position(|&skin| skin == self).unwrap();
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkinImages<T = graphics::Image> {
+    pub normal: T,
+    pub shooting: T,
+}
+
+// The images for `skin`, or the default skin's if it didn't load
+pub fn skin_images<T>(skins: &HashMap<Skin, SkinImages<T>>, skin: Skin) -> &SkinImages<T> {
+    skins.get(&skin).
+        or_else(|| skins.get(&Skin::DEFAULT)).
+        expect("the default skin is always loaded")
+}
 
 pub struct Assets {
-    pub ferris_normal_image:   graphics::Image,
-    pub ferris_shooting_image: graphics::Image,
-    pub shot_image:            graphics::Image,
+    pub skins: HashMap<Skin, SkinImages>,
+    pub shot_image: graphics::Image,
+
+    // Sounds are optional, the game still runs silently without them
+    pub shot_sound: Option<audio::Source>,
+    pub boom_sound: Option<audio::Source>,
+    pub music: Option<audio::Source>,
 
-    pub shot_sound: audio::Source,
-    pub boom_sound: audio::Source,
+    pub audio_settings: AudioSettings,
 }
 
 impl Assets {
+    pub const MAIN_FONT: &'static str = "MainFont";
+    pub const SHOT_VOLUME: f32 = 0.5;
+    pub const BOOM_VOLUME: f32 = 0.3;
+    pub const MUSIC_VOLUME: f32 = 0.4;
+
     pub fn new(ctx: &mut Context) -> GameResult<Assets> {
-        let ferris_normal_image   = graphics::Image::from_path(ctx, "/ferris-normal.png")?;
-        let ferris_shooting_image = graphics::Image::from_path(ctx, "/ferris-shooting.png")?;
-        let shot_image            = graphics::Image::from_path(ctx, "/shot.png")?;
+        // Fonts are registered with the graphics context once and referenced by name afterwards
+        let font_data = graphics::FontData::from_path(ctx, "/DejaVuSerif.ttf")?;
+        ctx.gfx.add_font(Self::MAIN_FONT, font_data);
 
-        let mut shot_sound = audio::Source::new(ctx, "/pew.ogg")?;
-        shot_sound.set_volume(0.5);
+        let skins = Self::load_skins(ctx);
+        let shot_image = load_image_or_placeholder(ctx, "/shot.png");
 
-        let mut boom_sound = audio::Source::new(ctx, "/boom.ogg")?;
-        boom_sound.set_volume(0.3);
+        let shot_sound = Self::load_sound(ctx, "/pew.ogg");
+        let boom_sound = Self::load_sound(ctx, "/boom.ogg");
+        let mut music = Self::load_sound(ctx, "/music.ogg");
+        if let Some(music) = &mut music {
+            music.set_repeat(true);
+        }
 
         Ok(Assets {
-            ferris_normal_image, ferris_shooting_image, shot_image,
-            shot_sound, boom_sound,
+            skins, shot_image,
+            shot_sound, boom_sound, music,
+            audio_settings: AudioSettings::default(),
         })
     }
+
+    // The default skin falls back to placeholders, the others are left out if either image is
+    // missing so they fall back to the default skin
+    fn load_skins(ctx: &mut Context) -> HashMap<Skin, SkinImages> {
+        let mut skins = HashMap::new();
+        for skin in Skin::ALL {
+            let (normal_path, shooting_path) = skin.image_paths();
+            if skin == Skin::DEFAULT {
+                let normal = load_image_or_placeholder(ctx, normal_path);
+                let shooting = load_image_or_placeholder(ctx, shooting_path);
+                skins.insert(skin, SkinImages { normal, shooting });
+                continue;
+            }
+
+            match (graphics::Image::from_path(ctx, normal_path), graphics::Image::from_path(ctx, shooting_path)) {
+                (Ok(normal), Ok(shooting)) => {
+                    skins.insert(skin, SkinImages { normal, shooting });
+                },
+                (Err(e), _) | (_, Err(e)) => {
+                    eprintln!("Warning: couldn't load the {} skin, using the default one: {}", skin.name(), e);
+                },
+            }
+        }
+        skins
+    }
+
+    pub fn skin_images(&self, skin: Skin) -> &SkinImages {
+        skin_images(&self.skins, skin)
+    }
+
+    fn load_sound(ctx: &mut Context, path: &str) -> Option<audio::Source> {
+        match audio::Source::new(ctx, path) {
+            Ok(sound) => Some(sound),
+            Err(e) => {
+                eprintln!("Warning: couldn't load sound {}: {}", path, e);
+                None
+            },
+        }
+    }
+
+    pub fn play_shoot(&mut self, ctx: &mut Context) {
+        Self::play(&mut self.shot_sound, Self::SHOT_VOLUME, self.audio_settings, ctx);
+    }
+
+    pub fn play_explosion(&mut self, ctx: &mut Context) {
+        Self::play(&mut self.boom_sound, Self::BOOM_VOLUME, self.audio_settings, ctx);
+    }
+
+    // Starts the music looping, or resumes it if it was paused. Does nothing if it's already
+    // playing, so there's only ever one track going.
+    pub fn play_music(&mut self, ctx: &mut Context) {
+        let volume = self.audio_settings.volume(Self::MUSIC_VOLUME);
+        if let Some(music) = &mut self.music {
+            music.set_volume(volume);
+            if music.paused() {
+                music.resume();
+            } else if !music.playing() {
+                let _ = music.play(ctx);
+            }
+        }
+    }
+
+    pub fn pause_music(&mut self) {
+        if let Some(music) = &self.music {
+            music.pause();
+        }
+    }
+
+    pub fn stop_music(&mut self, ctx: &mut Context) {
+        if let Some(music) = &mut self.music {
+            let _ = music.stop(ctx);
+        }
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.audio_settings.toggle_mute();
+        let volume = self.audio_settings.volume(Self::MUSIC_VOLUME);
+        if let Some(music) = &mut self.music {
+            music.set_volume(volume);
+        }
+    }
+
+    fn play(sound: &mut Option<audio::Source>, base_volume: f32, settings: AudioSettings, ctx: &mut Context) {
+        if let Some(sound) = sound {
+            settings.play(base_volume, |volume| {
+                sound.set_volume(volume);
+                let _ = sound.play(ctx);
+            });
+        }
+    }
+}
+
+pub const PLACEHOLDER_SIZE: u32 = 32;
+pub const PLACEHOLDER_COLOR: graphics::Color = graphics::Color::MAGENTA;
+
+// A missing image shows up as a magenta square instead of stopping the game from starting
+pub fn load_image_or_placeholder(ctx: &mut Context, path: &str) -> graphics::Image {
+    or_placeholder(graphics::Image::from_path(ctx, path), path, || {
+        graphics::Image::from_color(ctx, PLACEHOLDER_SIZE, PLACEHOLDER_SIZE, Some(PLACEHOLDER_COLOR))
+    })
+}
+
+pub fn or_placeholder<T, E: Display>(loaded: Result<T, E>, path: &str, placeholder: impl FnOnce() -> T) -> T {
+    loaded.unwrap_or_else(|e| {
+        eprintln!("Warning: couldn't load image {}, using a placeholder: {}", path, e);
+        placeholder()
+    })
+}
+
+// None if there's no file at `path`. A file that's there but can't be read gets a warning, so it
+// isn't mistaken for an empty one.
+pub fn read_text_file(ctx: &Context, path: &str) -> Option<String> {
+    let mut file = ctx.fs.open(path).ok()?;
+
+    let mut contents = String::new();
+    match file.read_to_string(&mut contents) {
+        Ok(_) => Some(contents),
+        Err(e) => {
+            eprintln!("Warning: couldn't read {}: {}", path, e);
+            None
+        },
+    }
 }
 
 pub trait Sprite: Debug {
     fn draw(&mut self, center: Point2<f32>, canvas: &mut graphics::Canvas);
-    fn width(&self, ctx: &mut Context) -> f32;
-    fn height(&self, ctx: &mut Context) -> f32;
+    fn width(&self) -> f32;
+    fn height(&self) -> f32;
+
+    /// Draws the sprite rotated around its center by `rotation` radians. Since the y axis points
+    /// down the screen, positive angles turn the sprite clockwise. Sprites that can't be rotated
+    /// draw unrotated.
+    fn draw_rotated(&mut self, top_left: Point2<f32>, _rotation: f32, canvas: &mut graphics::Canvas) {
+        self.draw(top_left, canvas)
+    }
+
+    // Multiplies the sprite's colors by `color`, white leaves it unchanged
+    fn draw_tinted(&mut self, top_left: Point2<f32>, _color: graphics::Color, canvas: &mut graphics::Canvas) {
+        self.draw(top_left, canvas)
+    }
+
+    fn update(&mut self, _seconds: f32) {}
 }
 
-#[derive(Debug)]
+// Draw params that place a sprite of the given size at `top_left`, rotated around its center
+fn rotated_draw_params(top_left: Point2<f32>, width: f32, height: f32, rotation: f32) -> graphics::DrawParam {
+    let center = Point2 {
+        x: top_left.x + width / 2.0,
+        y: top_left.y + height / 2.0,
+    };
+
+    graphics::DrawParam::default().
+        dest(center).
+        offset(Point2 { x: 0.5, y: 0.5 }).
+        rotation(rotation)
+}
+
+#[derive(Debug, Clone)]
 pub struct TextSprite {
     text: graphics::Text,
+    dimensions: graphics::Rect,
+    shadow: bool,
 }
 
 impl TextSprite {
-    pub fn new(label: &str) -> GameResult<TextSprite> {
+    pub const DEFAULT_SCALE: f32 = 32.0;
+    pub const SHADOW_OFFSET: Vector2<f32> = Vector2 { x: 2.0, y: 2.0 };
+    pub const SHADOW_COLOR: graphics::Color = graphics::Color::new(0.0, 0.0, 0.0, 0.7);
+
+    pub fn new(label: &str, font: &str, ctx: &mut Context) -> GameResult<TextSprite> {
+        Self::with_scale(label, font, Self::DEFAULT_SCALE, ctx)
+    }
+
+    pub fn with_scale(label: &str, font: &str, scale: f32, ctx: &mut Context) -> GameResult<TextSprite> {
         let mut text = graphics::Text::new(label);
 
-        text.set_font("MainFont");
-        text.set_scale(graphics::PxScale::from(32.0));
+        text.set_font(font);
+        text.set_scale(graphics::PxScale::from(scale));
+
+        // Measured up front so collision checks don't need a graphics context. Rounded up to whole
+        // pixels so the hitbox edges line up with the pixels the glyphs are drawn on.
+        let measured = text.dimensions(ctx).
+            ok_or_else(|| GameError::RenderError(format!("couldn't measure text `{}`", label)))?;
+        let dimensions = graphics::Rect::new(measured.x, measured.y, measured.w.ceil(), measured.h.ceil());
+
+        Ok(Self::from_text(text, dimensions))
+    }
+
+    // For text that's already been measured
+    pub fn from_text(text: graphics::Text, dimensions: graphics::Rect) -> TextSprite {
+        TextSprite { text, dimensions, shadow: false }
+    }
+
+    // Draws a dark copy of the text just behind it, so it stands out over whatever's underneath.
+    // The shadow isn't part of the hitbox.
+    pub fn with_shadow(mut self) -> Self {
+        self.shadow = true;
+        self
+    }
+
+    pub fn has_shadow(&self) -> bool {
+        self.shadow
+    }
+
+    fn draw_shadow(&self, top_left: Point2<f32>, rotation: f32, canvas: &mut graphics::Canvas) {
+        if !self.shadow {
+            return;
+        }
+
+        let shadow_pos = Point2 { x: top_left.x + Self::SHADOW_OFFSET.x, y: top_left.y + Self::SHADOW_OFFSET.y };
+        canvas.draw(&self.text, self.draw_params(shadow_pos, rotation).color(Self::SHADOW_COLOR))
+    }
+
+    // Every way of drawing the text goes through here, so the drawn text and the hitbox always
+    // line up. Only rotated text is turned around its center, upright text is drawn from its
+    // snapped corner.
+    fn draw_params(&self, top_left: Point2<f32>, rotation: f32) -> graphics::DrawParam {
+        if rotation == 0.0 {
+            return graphics::DrawParam::default().dest(text_origin(top_left));
+        }
 
-        Ok(TextSprite { text })
+        graphics::DrawParam::default().
+            dest(text_center(top_left, self.width(), self.height())).
+            offset(Point2 { x: 0.5, y: 0.5 }).
+            rotation(rotation)
+    }
+}
+
+// Where upright text whose box starts at `top_left` gets drawn from. It's snapped to whole pixels
+// so glyphs don't get smeared across two, which leaves the drawn text at most half a pixel off
+// from an unsnapped hitbox and exactly on a snapped one.
+pub fn text_origin(top_left: Point2<f32>) -> Point2<f32> {
+    Point2 { x: top_left.x.round(), y: top_left.y.round() }
+}
+
+// Rotated text turns around the middle of its box. It doesn't land on whole pixels while it's
+// turning anyway, so it isn't snapped.
+pub fn text_center(top_left: Point2<f32>, width: f32, height: f32) -> Point2<f32> {
+    Point2 {
+        x: top_left.x + width / 2.0,
+        y: top_left.y + height / 2.0,
     }
 }
 
 impl Sprite for TextSprite {
     fn draw(&mut self, top_left: Point2<f32>, canvas: &mut graphics::Canvas) {
-        canvas.draw(&self.text, graphics::DrawParam::default().dest(top_left))
+        self.draw_shadow(top_left, 0.0, canvas);
+        canvas.draw(&self.text, self.draw_params(top_left, 0.0))
+    }
+
+    fn draw_rotated(&mut self, top_left: Point2<f32>, rotation: f32, canvas: &mut graphics::Canvas) {
+        self.draw_shadow(top_left, rotation, canvas);
+        canvas.draw(&self.text, self.draw_params(top_left, rotation))
+    }
+
+    fn draw_tinted(&mut self, top_left: Point2<f32>, color: graphics::Color, canvas: &mut graphics::Canvas) {
+        self.draw_shadow(top_left, 0.0, canvas);
+        canvas.draw(&self.text, self.draw_params(top_left, 0.0).color(color))
+    }
+
+    fn width(&self) -> f32 { self.dimensions.w }
+    fn height(&self) -> f32 { self.dimensions.h }
+}
+
+#[derive(Debug)]
+pub struct ImageSprite {
+    image: graphics::Image,
+}
+
+impl ImageSprite {
+    pub fn new(image: graphics::Image) -> ImageSprite {
+        ImageSprite { image }
+    }
+}
+
+impl Sprite for ImageSprite {
+    fn draw(&mut self, top_left: Point2<f32>, canvas: &mut graphics::Canvas) {
+        canvas.draw(&self.image, graphics::DrawParam::default().dest(top_left))
+    }
+
+    fn draw_rotated(&mut self, top_left: Point2<f32>, rotation: f32, canvas: &mut graphics::Canvas) {
+        let draw_params = rotated_draw_params(top_left, self.width(), self.height(), rotation);
+        canvas.draw(&self.image, draw_params)
+    }
+
+    fn draw_tinted(&mut self, top_left: Point2<f32>, color: graphics::Color, canvas: &mut graphics::Canvas) {
+        canvas.draw(&self.image, graphics::DrawParam::default().dest(top_left).color(color))
+    }
+
+    fn width(&self) -> f32 { self.image.width() as f32 }
+    fn height(&self) -> f32 { self.image.height() as f32 }
+}
+
+#[derive(Debug, Clone)]
+pub struct Animation {
+    frame_count: usize,
+    frame_duration: f32,
+    elapsed: f32,
+    current_frame: usize,
+}
+
+impl Animation {
+    // A frame rate that isn't positive would never move on to the next frame, or never stop
+    pub fn new(frame_count: usize, frames_per_second: f32) -> GameResult<Animation> {
+        if !(frames_per_second > 0.0 && frames_per_second.is_finite()) {
+            return Err(GameError::CustomError(format!("animation frame rate must be positive, got {}", frames_per_second)));
+        }
+
+        Ok(Animation {
+            frame_count,
+            frame_duration: 1.0 / frames_per_second,
+            elapsed: 0.0,
+            current_frame: 0,
+        })
+    }
+
+    pub fn current_frame(&self) -> usize {
+        self.current_frame
+    }
+
+    pub fn update(&mut self, seconds: f32) {
+        if self.frame_count == 0 {
+            return;
+        }
+
+        self.elapsed += seconds;
+        while self.elapsed >= self.frame_duration {
+            self.elapsed -= self.frame_duration;
+            self.current_frame = (self.current_frame + 1) % self.frame_count;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AnimatedSprite {
+    frames: Vec<graphics::Image>,
+    animation: Animation,
+}
+
+impl AnimatedSprite {
+    // Needs at least one frame, since the size comes from the current one
+    pub fn new(frames: Vec<graphics::Image>, frames_per_second: f32) -> GameResult<AnimatedSprite> {
+        if frames.is_empty() {
+            return Err(GameError::CustomError(String::from("animated sprite has no frames")));
+        }
+
+        let animation = Animation::new(frames.len(), frames_per_second)?;
+        Ok(AnimatedSprite { frames, animation })
+    }
+
+    fn current_image(&self) -> &graphics::Image {
+        &self.frames[self.animation.current_frame()]
+    }
+}
+
+impl Sprite for AnimatedSprite {
+    fn draw(&mut self, top_left: Point2<f32>, canvas: &mut graphics::Canvas) {
+        canvas.draw(self.current_image(), graphics::DrawParam::default().dest(top_left))
+    }
+
+    fn draw_rotated(&mut self, top_left: Point2<f32>, rotation: f32, canvas: &mut graphics::Canvas) {
+        let draw_params = rotated_draw_params(top_left, self.width(), self.height(), rotation);
+        canvas.draw(self.current_image(), draw_params)
     }
 
-    fn width(&self, ctx: &mut Context) -> f32 { self.text.dimensions(ctx).unwrap().w }
-    fn height(&self, ctx: &mut Context) -> f32 { self.text.dimensions(ctx).unwrap().h }
+    fn draw_tinted(&mut self, top_left: Point2<f32>, color: graphics::Color, canvas: &mut graphics::Canvas) {
+        canvas.draw(self.current_image(), graphics::DrawParam::default().dest(top_left).color(color))
+    }
+
+    fn width(&self) -> f32 { self.current_image().width() as f32 }
+    fn height(&self) -> f32 { self.current_image().height() as f32 }
+
+    fn update(&mut self, seconds: f32) {
+        self.animation.update(seconds);
+    }
 }
@@ -0,0 +1,174 @@
+use ggez::mint::{Point2, Vector2};
+use ggez::{Context, GameResult};
+
+use crate::assets::{Assets, Sprite, TextSprite};
+use crate::collision::Collidable;
+use crate::entities::{Enemy, EnemyEvent, EnemyKind, Shot};
+use crate::timeline::{Action, Timeline};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BossPhase {
+    One,
+    Two,
+    Three,
+}
+
+impl BossPhase {
+    // Health fraction at or below which each later phase starts
+    pub const PHASE_TWO_THRESHOLD: f32 = 2.0 / 3.0;
+    pub const PHASE_THREE_THRESHOLD: f32 = 1.0 / 3.0;
+
+    pub fn for_health(fraction: f32) -> BossPhase {
+        if fraction <= Self::PHASE_THREE_THRESHOLD {
+            BossPhase::Three
+        } else if fraction <= Self::PHASE_TWO_THRESHOLD {
+            BossPhase::Two
+        } else {
+            BossPhase::One
+        }
+    }
+
+    pub fn sweep_speed(self) -> f32 {
+        match self {
+            BossPhase::One   => 150.0,
+            BossPhase::Two   => 250.0,
+            BossPhase::Three => 350.0,
+        }
+    }
+
+    pub fn volley_interval(self) -> f32 {
+        match self {
+            BossPhase::One   => 2.0,
+            BossPhase::Two   => 1.5,
+            BossPhase::Three => 1.0,
+        }
+    }
+
+    pub fn volley_size(self) -> usize {
+        match self {
+            BossPhase::One   => 3,
+            BossPhase::Two   => 5,
+            BossPhase::Three => 7,
+        }
+    }
+}
+
+// Comes down to `HOVER_Y`, then sweeps back and forth across the screen firing fans of shots.
+// It gets faster and fires more as it takes damage.
+#[derive(Debug)]
+pub struct Boss {
+    pub enemy: Enemy,
+    pub phase: BossPhase,
+    max_health: u32,
+    direction: f32,
+    volley_timer: f32,
+}
+
+impl Boss {
+    pub const HOVER_Y: f32 = 80.0;
+    pub const ENTRY_SPEED: f32 = 100.0;
+    pub const VOLLEY_SPREAD: f32 = 0.15;
+    pub const ATTACK_LOOP_LENGTH: f32 = 8.0;
+    pub const LUNGE_TIME: f32 = 4.0;
+    pub const LUNGE_RETURN_DELAY: f32 = 0.5;
+    pub const LUNGE_IMPULSE: f32 = 400.0;
+    pub const WIDE_VOLLEY_TIME: f32 = 6.0;
+    pub const WIDE_VOLLEY_SIZE: usize = 11;
+    pub const WIDE_VOLLEY_SPREAD: f32 = 0.25;
+
+    pub fn spawn(screen_width: f32, ctx: &mut Context) -> GameResult<Boss> {
+        let stats = EnemyKind::Boss.stats();
+        let sprite = Box::new(TextSprite::new(stats.label, Assets::MAIN_FONT, ctx)?.with_shadow());
+        Boss::with_sprite(screen_width, sprite)
+    }
+
+    pub fn with_sprite(screen_width: f32, sprite: Box<dyn Sprite>) -> GameResult<Boss> {
+        let stats = EnemyKind::Boss.stats();
+        let pos = Point2 { x: (screen_width - sprite.width()) / 2.0, y: -sprite.height() };
+
+        // The boss moves itself instead of using the enemy's velocity
+        let enemy = EnemyKind::Boss.build(stats.label, pos, 0.0, sprite)?.with_timeline(Boss::attack_timeline());
+        Ok(Boss::new(enemy))
+    }
+
+    pub fn new(enemy: Enemy) -> Boss {
+        Boss {
+            max_health: enemy.health,
+            enemy,
+            phase: BossPhase::One,
+            direction: 1.0,
+            volley_timer: BossPhase::One.volley_interval(),
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.enemy.is_alive
+    }
+
+    pub fn health_fraction(&self) -> f32 {
+        self.enemy.health as f32 / self.max_health as f32
+    }
+
+    // Returns whether the boss was destroyed. Moves on to the next phase when the health drops
+    // past its threshold, and never goes back.
+    pub fn take_damage(&mut self, damage: u32) -> bool {
+        let destroyed = self.enemy.hit(damage);
+
+        let phase = BossPhase::for_health(self.health_fraction());
+        if phase > self.phase {
+            self.phase = phase;
+            self.volley_timer = f32::min(self.volley_timer, phase.volley_interval());
+        }
+
+        destroyed
+    }
+
+    // Returns the shots fired during this update
+    pub fn update(&mut self, seconds: f32, screen_width: f32) -> Vec<Shot> {
+        self.enemy.update(seconds);
+
+        if self.enemy.pos.y < Self::HOVER_Y {
+            self.enemy.pos.y = f32::min(self.enemy.pos.y + Self::ENTRY_SPEED * seconds, Self::HOVER_Y);
+            return Vec::new();
+        }
+
+        let max_x = f32::max(screen_width - self.enemy.bounding_rect().w, 0.0);
+        self.enemy.pos.x += self.direction * self.phase.sweep_speed() * seconds;
+        if self.enemy.pos.x <= 0.0 {
+            self.enemy.pos.x = 0.0;
+            self.direction = 1.0;
+        } else if self.enemy.pos.x >= max_x {
+            self.enemy.pos.x = max_x;
+            self.direction = -1.0;
+        }
+
+        let mut shots: Vec<Shot> = self.enemy.update_timeline(seconds).into_iter().filter_map(|event| match event {
+            EnemyEvent::FiredShot(shot) => Some(shot),
+            _ => None,
+        }).collect();
+
+        self.volley_timer -= seconds;
+        if self.volley_timer <= 0.0 {
+            self.volley_timer += self.phase.volley_interval();
+            shots.extend(self.volley());
+        }
+        shots
+    }
+
+    // Every so often the boss lunges down at the player and backs off again, then follows up
+    // with a wide volley
+    pub fn attack_timeline() -> Timeline {
+        Timeline::new(vec![
+            (Self::LUNGE_TIME, Action::Dash(Vector2 { x: 0.0, y: Self::LUNGE_IMPULSE })),
+            (Self::LUNGE_TIME + Self::LUNGE_RETURN_DELAY, Action::Dash(Vector2 { x: 0.0, y: -Self::LUNGE_IMPULSE })),
+            (Self::WIDE_VOLLEY_TIME, Action::FireVolley { count: Self::WIDE_VOLLEY_SIZE, spread: Self::WIDE_VOLLEY_SPREAD }),
+        ]).with_loop(Self::ATTACK_LOOP_LENGTH)
+    }
+
+    // A fan of shots aimed down, centered under the boss
+    fn volley(&self) -> Vec<Shot> {
+        let rect = self.enemy.bounding_rect();
+        let muzzle = Point2 { x: rect.x + rect.w / 2.0, y: rect.bottom() };
+        Shot::volley(muzzle, self.phase.volley_size(), Self::VOLLEY_SPREAD)
+    }
+}
@@ -0,0 +1,153 @@
+use ggez::graphics;
+use ggez::mint::{Point2, Vector2};
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::f32::consts::PI;
+
+use crate::screen::ScreenConfig;
+
+// Follows the player up and down a play area that's taller than the screen. `pos` is the top left
+// of the view in world space, and is kept inside the world so nothing past its edges is shown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    pub pos: Point2<f32>,
+    pub view: ScreenConfig,
+    pub world: ScreenConfig,
+}
+
+impl Camera {
+    // How quickly the view catches up with its target, per second. Each update closes `SMOOTHING`
+    // times the elapsed seconds of the gap, all of it once that reaches 1.
+    pub const SMOOTHING: f32 = 5.0;
+
+    pub fn new(view: ScreenConfig, world: ScreenConfig) -> Self {
+        Camera { pos: Point2 { x: 0.0, y: 0.0 }, view, world }
+    }
+
+    // Where the view would be centered on `target`, kept inside the world
+    fn aim(&self, target: Point2<f32>) -> Point2<f32> {
+        let max_y = f32::max(self.world.height - self.view.height, 0.0);
+        Point2 {
+            x: 0.0,
+            y: (target.y - self.view.height / 2.0).clamp(0.0, max_y),
+        }
+    }
+
+    // Jumps straight to `target`, like at the start of a game
+    pub fn center_on(&mut self, target: Point2<f32>) {
+        self.pos = self.aim(target);
+    }
+
+    // Only follows vertically, the world is never wider than the screen
+    pub fn follow(&mut self, target: Point2<f32>, seconds: f32) {
+        let aim = self.aim(target);
+        let amount = f32::min(Self::SMOOTHING * seconds, 1.0);
+        self.pos.y += (aim.y - self.pos.y) * amount;
+    }
+
+    // The offset to apply to anything drawn in world space
+    pub fn transform(&self) -> Vector2<f32> {
+        Vector2 { x: -self.pos.x, y: -self.pos.y }
+    }
+
+    // The rect of the world that's on screen, with `shake` added on top
+    pub fn view_rect(&self, shake: Vector2<f32>) -> graphics::Rect {
+        let transform = self.transform();
+        graphics::Rect::new(-transform.x - shake.x, -transform.y - shake.y, self.view.width, self.view.height)
+    }
+
+    pub fn to_world(&self, screen_point: Point2<f32>) -> Point2<f32> {
+        Point2 { x: screen_point.x + self.pos.x, y: screen_point.y + self.pos.y }
+    }
+
+    pub fn to_screen(&self, world_point: Point2<f32>) -> Point2<f32> {
+        Point2 { x: world_point.x - self.pos.x, y: world_point.y - self.pos.y }
+    }
+}
+
+#[derive(Debug)]
+pub struct ScreenShake {
+    rng: StdRng,
+    intensity: f32,
+    time_left: f32,
+}
+
+impl ScreenShake {
+    pub const DECAY_TIME: f32 = 0.3;
+    pub const MAX_INTENSITY: f32 = 20.0;
+
+    pub fn new(rng: StdRng) -> Self {
+        ScreenShake {
+            rng,
+            intensity: 0.0,
+            time_left: 0.0,
+        }
+    }
+
+    // A stronger shake overrides a weaker one that's still going, but a weaker one doesn't cut a
+    // stronger one short
+    pub fn trigger(&mut self, intensity: f32) {
+        let current = self.intensity * self.time_left / Self::DECAY_TIME;
+        self.intensity = current.max(intensity).min(Self::MAX_INTENSITY);
+        self.time_left = Self::DECAY_TIME;
+    }
+
+    pub fn is_shaking(&self) -> bool {
+        self.time_left > 0.0
+    }
+
+    // Returns the offset to apply to the draw transform this frame
+    pub fn update(&mut self, seconds: f32) -> Vector2<f32> {
+        self.time_left = (self.time_left - seconds).max(0.0);
+        if !self.is_shaking() {
+            self.intensity = 0.0;
+            return Vector2 { x: 0.0, y: 0.0 };
+        }
+
+        let magnitude = self.intensity * self.time_left / Self::DECAY_TIME;
+        let angle = self.rng.gen_range(0.0..2.0 * PI);
+
+        Vector2 {
+            x: magnitude * angle.cos(),
+            y: magnitude * angle.sin(),
+        }
+    }
+}
+
+// A red tint over the whole screen when the player gets hurt, fading out from `MAX_ALPHA`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DamageFlash {
+    time_left: f32,
+}
+
+impl DamageFlash {
+    pub const DURATION: f32 = 0.4;
+    // Kept well short of opaque so it doesn't hide what hit you
+    pub const MAX_ALPHA: f32 = 0.35;
+    pub const COLOR: graphics::Color = graphics::Color::new(1.0, 0.0, 0.0, 1.0);
+
+    pub fn new() -> Self {
+        DamageFlash::default()
+    }
+
+    pub fn trigger(&mut self) {
+        self.time_left = Self::DURATION;
+    }
+
+    pub fn update(&mut self, seconds: f32) {
+        self.time_left = f32::max(self.time_left - seconds, 0.0);
+    }
+
+    pub fn alpha(&self) -> f32 {
+        Self::MAX_ALPHA * self.time_left / Self::DURATION
+    }
+
+    pub fn draw(&self, screen: graphics::Rect, canvas: &mut graphics::Canvas) {
+        if self.time_left <= 0.0 {
+            return;
+        }
+
+        let color = graphics::Color { a: self.alpha(), ..Self::COLOR };
+        canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(screen).color(color));
+    }
+}
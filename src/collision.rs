@@ -0,0 +1,201 @@
+use ggez::graphics;
+use ggez::mint::Point2;
+use std::cell::Cell;
+use std::collections::HashMap;
+
+pub trait Collidable {
+    fn bounding_rect(&self) -> graphics::Rect;
+}
+
+// Rects that only share an edge don't count as overlapping
+pub fn rect_overlap(a: graphics::Rect, b: graphics::Rect) -> bool {
+    a.left() < b.right() && b.left() < a.right() &&
+        a.top() < b.bottom() && b.top() < a.bottom()
+}
+
+// Points on the left and top edges are inside, points on the right and bottom edges aren't
+pub fn point_in_rect(p: Point2<f32>, r: graphics::Rect) -> bool {
+    p.x >= r.left() && p.x < r.right() &&
+        p.y >= r.top() && p.y < r.bottom()
+}
+
+// Whether the segment from `a` to `b` passes through the inside of the rect, using the slab method
+pub fn segment_intersects_rect(a: Point2<f32>, b: Point2<f32>, r: graphics::Rect) -> bool {
+    let mut t_min: f32 = 0.0;
+    let mut t_max: f32 = 1.0;
+
+    for (start, delta, low, high) in [(a.x, b.x - a.x, r.left(), r.right()), (a.y, b.y - a.y, r.top(), r.bottom())] {
+        if delta == 0.0 {
+            // Parallel to this slab, so it has to already be between its edges
+            if start <= low || start >= high {
+                return false;
+            }
+            continue;
+        }
+
+        let t1 = (low - start) / delta;
+        let t2 = (high - start) / delta;
+        t_min = t_min.max(t1.min(t2));
+        t_max = t_max.min(t1.max(t2));
+    }
+
+    t_min < t_max
+}
+
+pub fn collides<A: Collidable + ?Sized, B: Collidable + ?Sized>(a: &A, b: &B) -> bool {
+    rect_overlap(a.bounding_rect(), b.bounding_rect())
+}
+
+// Buckets rects into fixed-size cells, so a query only has to look at rects sharing a cell with it
+#[derive(Debug)]
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<(usize, graphics::Rect)>>,
+    comparisons: Cell<usize>,
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> Self {
+        SpatialGrid {
+            cell_size,
+            cells: HashMap::new(),
+            comparisons: Cell::new(0),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    pub fn insert(&mut self, id: usize, rect: graphics::Rect) {
+        for cell in self.covered_cells(rect) {
+            self.cells.entry(cell).or_default().push((id, rect));
+        }
+    }
+
+    // Ids of every inserted rect overlapping `rect`, in ascending order
+    pub fn query(&self, rect: graphics::Rect) -> Vec<usize> {
+        let mut ids = Vec::new();
+
+        for cell in self.covered_cells(rect) {
+            for &(id, other) in self.cells.get(&cell).into_iter().flatten() {
+                self.comparisons.set(self.comparisons.get() + 1);
+                if rect_overlap(rect, other) {
+                    ids.push(id);
+                }
+            }
+        }
+
+        // Rects spanning several cells are found once per cell
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    // Number of rect comparisons done by queries so far
+    pub fn comparisons(&self) -> usize {
+        self.comparisons.get()
+    }
+
+    fn covered_cells(&self, rect: graphics::Rect) -> impl Iterator<Item = (i32, i32)> {
+        let min_x = (rect.left() / self.cell_size).floor() as i32;
+        let max_x = (rect.right() / self.cell_size).floor() as i32;
+        let min_y = (rect.top() / self.cell_size).floor() as i32;
+        let max_y = (rect.bottom() / self.cell_size).floor() as i32;
+
+        (min_x ..= max_x).flat_map(move |x| (min_y ..= max_y).map(move |y| (x, y)))
+    }
+}
+
+// Splits the area into quarters wherever rects pile up, so clusters get divided finely while empty
+// space stays as one big node. Rects that straddle a split stay in the node above it, and rects
+// outside the area stay at the root.
+#[derive(Debug)]
+pub struct QuadTree {
+    bounds: graphics::Rect,
+    depth: usize,
+    items: Vec<(usize, graphics::Rect)>,
+    children: Option<Box<[QuadTree; 4]>>,
+}
+
+impl QuadTree {
+    pub const MAX_DEPTH: usize = 6;
+    // Rects a node holds before it splits
+    pub const NODE_CAPACITY: usize = 8;
+
+    pub fn new(bounds: graphics::Rect) -> Self {
+        Self::with_depth(bounds, 0)
+    }
+
+    fn with_depth(bounds: graphics::Rect, depth: usize) -> Self {
+        QuadTree {
+            bounds,
+            depth,
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.children = None;
+    }
+
+    pub fn insert(&mut self, id: usize, rect: graphics::Rect) {
+        if let Some(child) = self.child_containing(rect) {
+            child.insert(id, rect);
+            return;
+        }
+
+        self.items.push((id, rect));
+        if self.children.is_none() && self.items.len() > Self::NODE_CAPACITY && self.depth < Self::MAX_DEPTH {
+            self.split();
+        }
+    }
+
+    // Ids of every inserted rect overlapping `rect`, in ascending order
+    pub fn query(&self, rect: graphics::Rect) -> Vec<usize> {
+        let mut ids = Vec::new();
+        self.collect(rect, &mut ids);
+        ids.sort_unstable();
+        ids
+    }
+
+    fn collect(&self, rect: graphics::Rect, ids: &mut Vec<usize>) {
+        for &(id, other) in self.items.iter() {
+            if rect_overlap(rect, other) {
+                ids.push(id);
+            }
+        }
+
+        for child in self.children.iter().flat_map(|children| children.iter()) {
+            if rect_overlap(rect, child.bounds) {
+                child.collect(rect, ids);
+            }
+        }
+    }
+
+    fn split(&mut self) {
+        let (x, y) = (self.bounds.x, self.bounds.y);
+        let (w, h) = (self.bounds.w / 2.0, self.bounds.h / 2.0);
+        let depth = self.depth + 1;
+        self.children = Some(Box::new([
+            QuadTree::with_depth(graphics::Rect::new(x, y, w, h), depth),
+            QuadTree::with_depth(graphics::Rect::new(x + w, y, w, h), depth),
+            QuadTree::with_depth(graphics::Rect::new(x, y + h, w, h), depth),
+            QuadTree::with_depth(graphics::Rect::new(x + w, y + h, w, h), depth),
+        ]));
+
+        for (id, rect) in std::mem::take(&mut self.items) {
+            self.insert(id, rect);
+        }
+    }
+
+    fn child_containing(&mut self, rect: graphics::Rect) -> Option<&mut QuadTree> {
+        self.children.as_mut()?.iter_mut().find(|child| {
+            let bounds = child.bounds;
+            rect.left() >= bounds.left() && rect.right() <= bounds.right() &&
+                rect.top() >= bounds.top() && rect.bottom() <= bounds.bottom()
+        })
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::VecDeque;
+
+#[derive(Debug, Default, Clone)]
+pub struct Difficulty {
+    pub elapsed: f32,
+    pub performance: PerformanceTracker,
+}
+
+impl Difficulty {
+    pub const START_SPAWN_INTERVAL: f32 = 2.0;
+    pub const MIN_SPAWN_INTERVAL: f32 = 0.4;
+    pub const START_SPEED_MULTIPLIER: f32 = 1.0;
+    pub const MAX_SPEED_MULTIPLIER: f32 = 2.5;
+    // Seconds of survival until the game is as hard as it gets
+    pub const RAMP_DURATION: f32 = 180.0;
+
+    pub fn new() -> Self {
+        Difficulty::default()
+    }
+
+    pub fn update(&mut self, seconds: f32) {
+        self.elapsed += seconds;
+        self.performance.update(seconds);
+    }
+
+    // Spawns come faster for players who are doing well, but never faster than the minimum
+    pub fn enemy_spawn_interval(&self) -> f32 {
+        let interval = lerp(Self::START_SPAWN_INTERVAL, Self::MIN_SPAWN_INTERVAL, self.progress());
+        f32::max(interval / self.performance.multiplier(), Self::MIN_SPAWN_INTERVAL)
+    }
+
+    pub fn enemy_speed_multiplier(&self) -> f32 {
+        lerp(Self::START_SPEED_MULTIPLIER, Self::MAX_SPEED_MULTIPLIER, self.progress())
+    }
+
+    // Smoothstep from 0 to 1 over the ramp, so difficulty eases in and out of its extremes
+    fn progress(&self) -> f32 {
+        let t = f32::clamp(self.elapsed / Self::RAMP_DURATION, 0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+// Remembers the kills and deaths of the last `WINDOW` seconds, to tell how well the player is doing
+#[derive(Debug, Default, Clone)]
+pub struct PerformanceTracker {
+    elapsed: f32,
+    kills: VecDeque<f32>,
+    deaths: VecDeque<f32>,
+}
+
+impl PerformanceTracker {
+    pub const WINDOW: f32 = 20.0;
+    // Kills per second that count as doing as well as possible
+    pub const TARGET_KILL_RATE: f32 = 0.5;
+    // How far the multiplier can move away from 1 either way
+    pub const MAX_ADJUSTMENT: f32 = 0.3;
+
+    pub fn new() -> Self {
+        PerformanceTracker::default()
+    }
+
+    pub fn update(&mut self, seconds: f32) {
+        self.elapsed += seconds;
+
+        let cutoff = self.elapsed - Self::WINDOW;
+        for events in [&mut self.kills, &mut self.deaths] {
+            while events.front().is_some_and(|&time| time < cutoff) {
+                events.pop_front();
+            }
+        }
+    }
+
+    pub fn record_kill(&mut self) {
+        self.kills.push_back(self.elapsed);
+    }
+
+    pub fn record_death(&mut self) {
+        self.deaths.push_back(self.elapsed);
+    }
+
+    pub fn kill_rate(&self) -> f32 {
+        self.kills.len() as f32 / Self::WINDOW
+    }
+
+    // Above 1 makes the game harder. A recent death always eases off, otherwise it goes up with the
+    // kill rate.
+    pub fn multiplier(&self) -> f32 {
+        if !self.deaths.is_empty() {
+            return 1.0 - Self::MAX_ADJUSTMENT;
+        }
+
+        let skill = f32::min(self.kill_rate() / Self::TARGET_KILL_RATE, 1.0);
+        1.0 + Self::MAX_ADJUSTMENT * skill
+    }
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from * (1.0 - t) + to * t
+}
@@ -2,53 +2,552 @@ use ggez::{Context, GameResult};
 use ggez::graphics;
 use ggez::mint::{Vector2, Point2};
 
-use crate::assets::{Assets, Sprite};
+use crate::assets::{Assets, Skin, SkinImages, Sprite, TextSprite};
+use crate::tuning::Tuning;
+use crate::collision::{collides, rect_overlap, segment_intersects_rect, Collidable};
+use crate::movement::{MovementPattern, StraightDown};
+use crate::screen::ScreenConfig;
+use crate::timeline::{Action, Timeline};
+
+use std::f32::consts::PI;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Lets shots remember which enemies they've already gone through
+static NEXT_ENEMY_ID: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Debug)]
 pub enum PlayerState {
     Normal,
     Shooting,
+    Dying,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeaponMode {
+    Single,
+    Spread,
+    Homing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MovementBounds {
+    #[default]
+    Clamp,
+    Wrap,
+}
+
+// What happened during an update, for the game to react to
+#[derive(Debug, Clone)]
+pub enum PlayerEvent {
+    FiredShot(Shot),
+    Respawned,
+}
+
+#[derive(Debug, Clone)]
+pub enum EnemyEvent {
+    FiredShot(Shot),
+    ReachedBottom,
 }
 
 #[derive(Debug)]
 pub struct Player {
     pub state: PlayerState,
     pub pos: Point2<f32>,
+    pub velocity: Vector2<f32>,
     pub time_until_next_shot: f32,
+    pub health: i32,
+    pub lives: u32,
+    pub invuln_timer: f32,
+    pub weapon_mode: WeaponMode,
+    pub movement_bounds: MovementBounds,
+    pub charge: f32,
+    pub rapid_fire_timer: f32,
+    pub pierce_count: u32,
+    pub dying_timer: f32,
+    pub shoot_anim_timer: f32,
+    pub bombs: u32,
+    pub tilt: f32,
+    pub fire_held: bool,
+    // Time left to fire a press that came in during the cooldown
+    pub fire_buffer: f32,
+    pub ammo: u32,
+    // Counts down while reloading, firing resumes with a full magazine once it runs out
+    pub reload_timer: f32,
+    // Goes up with every shot and cools down over time
+    pub heat: f32,
+    // Set when the heat reaches `MAX_HEAT`, and stays set until it cools below `OVERHEAT_RESET`
+    pub overheated: bool,
+    pub skin: Skin,
+    // Holding fire keeps shooting instead of charging
+    pub autofire: bool,
+    pub tuning: Tuning,
+    pub spawn_pos: Point2<f32>,
 }
 
 impl Player {
     pub const SHOT_TIMEOUT: f32 = 1.0;
     pub const SPEED: f32 = 500.0;
+    pub const ACCELERATION: f32 = 3000.0;
+    pub const FRICTION: f32 = 2000.0;
+    pub const MAX_HEALTH: i32 = 3;
+    pub const STARTING_LIVES: u32 = 3;
+    pub const INVULN_TIME: f32 = 2.0;
+    pub const SPREAD_ANGLE: f32 = 0.2;
+    pub const MAX_CHARGE: f32 = 1.5;
+    pub const RAPID_FIRE_TIMEOUT: f32 = 0.3;
+    pub const DEATH_TIME: f32 = 1.0;
+    pub const SHOOT_ANIM_TIME: f32 = 0.2;
+    pub const FIRE_BUFFER_TIME: f32 = 0.15;
+    pub const MAG_SIZE: u32 = 12;
+    // How close an enemy shot has to come to count as a graze
+    pub const GRAZE_MARGIN: f32 = 30.0;
+    pub const RELOAD_TIME: f32 = 1.5;
+    pub const MAX_HEAT: f32 = 1.0;
+    pub const HEAT_PER_SHOT: f32 = 0.25;
+    // Heat lost per second
+    pub const COOL_RATE: f32 = 0.3;
+    pub const OVERHEAT_RESET: f32 = 0.4;
+    pub const STARTING_BOMBS: u32 = 2;
+    // Radians, reached when moving at full speed
+    pub const MAX_TILT: f32 = 0.2;
+    // How quickly the tilt turns toward its target, per second. Each update covers `TILT_RATE`
+    // times the elapsed seconds of the way there, all of it once that reaches 1.
+    pub const TILT_RATE: f32 = 10.0;
+
+    // Size of the normal sprite as drawn, which is scaled down slightly
+    pub const WIDTH: f32 = 150.0 * 0.95;
+    pub const HEIGHT: f32 = 100.0 * 0.95;
 
     pub fn new(pos: Point2<f32>) -> Self {
         Player {
             state: PlayerState::Normal,
             pos,
+            velocity: Vector2 { x: 0.0, y: 0.0 },
             time_until_next_shot: Self::SHOT_TIMEOUT,
+            health: Self::MAX_HEALTH,
+            lives: Self::STARTING_LIVES,
+            invuln_timer: 0.0,
+            weapon_mode: WeaponMode::Single,
+            movement_bounds: MovementBounds::default(),
+            charge: 0.0,
+            rapid_fire_timer: 0.0,
+            pierce_count: 0,
+            dying_timer: 0.0,
+            shoot_anim_timer: 0.0,
+            bombs: Self::STARTING_BOMBS,
+            tilt: 0.0,
+            fire_held: false,
+            fire_buffer: 0.0,
+            ammo: Self::MAG_SIZE,
+            reload_timer: 0.0,
+            heat: 0.0,
+            overheated: false,
+            skin: Skin::DEFAULT,
+            autofire: false,
+            tuning: Tuning::default(),
+            spawn_pos: pos,
+        }
+    }
+
+    pub fn with_skin(mut self, skin: Skin) -> Self {
+        self.skin = skin;
+        self
+    }
+
+    pub fn with_autofire(mut self, autofire: bool) -> Self {
+        self.autofire = autofire;
+        self
+    }
+
+    pub fn with_tuning(mut self, tuning: Tuning) -> Self {
+        self.tuning = tuning;
+        self.time_until_next_shot = tuning.shot_timeout;
+        self
+    }
+
+    pub fn muzzle_position(&self) -> Point2<f32> {
+        Point2 {
+            x: self.pos.x - 75.0,
+            y: self.pos.y - 80.0,
+        }
+    }
+
+    fn shot_velocity(&self, angle: f32) -> Vector2<f32> {
+        Shot::direction_velocity_at(angle, self.tuning.shot_speed)
+    }
+
+    pub fn spawn_shots(&self) -> Vec<Shot> {
+        let muzzle = self.muzzle_position();
+
+        match self.weapon_mode {
+            WeaponMode::Single => vec![Shot::new(muzzle, self.shot_velocity(0.0))],
+
+            WeaponMode::Spread => vec![
+                Shot::new(muzzle, self.shot_velocity(-Self::SPREAD_ANGLE)),
+                Shot::new(muzzle, self.shot_velocity(0.0)),
+                Shot::new(muzzle, self.shot_velocity(Self::SPREAD_ANGLE)),
+            ],
+
+            WeaponMode::Homing => vec![Shot::new(muzzle, self.shot_velocity(0.0)).with_homing()],
+        }
+    }
+
+    // Fires whatever the weapon mode and charge make, or nothing if the weapon can't fire yet. A
+    // shot that couldn't go out is buffered for when it can.
+    pub fn shoot(&mut self) -> Vec<Shot> {
+        if !self.can_shoot() {
+            self.buffer_fire();
+            return Vec::new();
+        }
+
+        let shots = self.release_charge();
+        self.start_cooldown();
+        self.use_ammo();
+        self.add_heat();
+        self.show_shooting();
+        shots
+    }
+
+    // Shows the firing pose for `SHOOT_ANIM_TIME` before going back to normal
+    pub fn show_shooting(&mut self) {
+        self.state = PlayerState::Shooting;
+        self.shoot_anim_timer = Self::SHOOT_ANIM_TIME;
+    }
+
+    // Remembers a press that came in too early, so it goes off once the cooldown is over
+    fn buffer_fire(&mut self) {
+        if !self.is_dying() {
+            self.fire_buffer = Self::FIRE_BUFFER_TIME;
+        }
+    }
+
+    // Every shot takes one round, however many bullets it spreads into. Emptying the magazine
+    // starts a reload straight away.
+    fn use_ammo(&mut self) {
+        self.ammo = self.ammo.saturating_sub(1);
+        if self.ammo == 0 {
+            self.reload_timer = Self::RELOAD_TIME;
+        }
+    }
+
+    // Like ammo, a spread counts as one shot
+    fn add_heat(&mut self) {
+        self.heat = f32::min(self.heat + Self::HEAT_PER_SHOT, Self::MAX_HEAT);
+        if self.heat >= Self::MAX_HEAT {
+            self.overheated = true;
+        }
+    }
+
+    fn cool_down(&mut self, seconds: f32) {
+        self.heat = f32::max(self.heat - Self::COOL_RATE * seconds, 0.0);
+        if self.overheated && self.heat < Self::OVERHEAT_RESET {
+            self.overheated = false;
+        }
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.reload_timer > 0.0
+    }
+
+    pub fn start_cooldown(&mut self) {
+        self.time_until_next_shot = self.shot_timeout();
+    }
+
+    pub fn charge_up(&mut self, seconds: f32) {
+        self.charge = f32::min(self.charge + seconds, Self::MAX_CHARGE);
+    }
+
+    // A tap does normal damage, half a charge doubles it and a full charge triples it
+    pub fn charge_damage(&self) -> u32 {
+        if self.charge >= Self::MAX_CHARGE {
+            3
+        } else if self.charge >= Self::MAX_CHARGE / 2.0 {
+            2
+        } else {
+            1
+        }
+    }
+
+    // Fires the charged up shots, which grow up to twice as large with charge
+    pub fn release_charge(&mut self) -> Vec<Shot> {
+        let damage = self.charge_damage();
+        let scale = 1.0 + self.charge / Self::MAX_CHARGE;
+        self.charge = 0.0;
+
+        self.spawn_shots().
+            into_iter().
+            map(|shot| shot.with_power(damage, scale).with_pierce(self.pierce_count)).
+            collect()
+    }
+
+    pub fn take_damage(&mut self, amount: i32) {
+        if self.is_invulnerable() || self.is_dead() {
+            return;
+        }
+
+        self.health -= amount;
+        if self.health <= 0 {
+            self.lives -= 1;
+
+            if self.lives > 0 {
+                self.health = Self::MAX_HEALTH;
+                self.state = PlayerState::Dying;
+                self.dying_timer = Self::DEATH_TIME;
+                self.charge = 0.0;
+            } else {
+                self.health = 0;
+            }
+        }
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invuln_timer > 0.0 || self.is_dying()
+    }
+
+    // The hit box grown by `GRAZE_MARGIN` on every side
+    pub fn graze_rect(&self) -> graphics::Rect {
+        let rect = self.bounding_rect();
+        graphics::Rect::new(
+            rect.x - Self::GRAZE_MARGIN,
+            rect.y - Self::GRAZE_MARGIN,
+            rect.w + Self::GRAZE_MARGIN * 2.0,
+            rect.h + Self::GRAZE_MARGIN * 2.0,
+        )
+    }
+
+    // An enemy shot passing close by without hitting counts as a graze, once per shot. There's no
+    // risk while invulnerable, so no reward either. Returns whether this was a new graze.
+    pub fn graze(&self, shot: &mut Shot) -> bool {
+        if !shot.is_alive || shot.grazed || self.is_invulnerable() {
+            return false;
+        }
+
+        let shot_rect = shot.bounding_rect();
+        if !rect_overlap(shot_rect, self.graze_rect()) || rect_overlap(shot_rect, self.bounding_rect()) {
+            return false;
+        }
+
+        shot.grazed = true;
+        true
+    }
+
+    // Cooldown to wait after firing, shortened while rapid fire is active
+    pub fn shot_timeout(&self) -> f32 {
+        if self.rapid_fire_timer > 0.0 {
+            Self::RAPID_FIRE_TIMEOUT
+        } else {
+            self.tuning.shot_timeout
+        }
+    }
+
+    // Returns whether there was a bomb to use
+    pub fn use_bomb(&mut self) -> bool {
+        if self.bombs == 0 || self.is_dying() {
+            return false;
+        }
+
+        self.bombs -= 1;
+        true
+    }
+
+    pub fn is_dying(&self) -> bool {
+        matches!(self.state, PlayerState::Dying)
+    }
+
+    pub fn can_shoot(&self) -> bool {
+        self.time_until_next_shot <= 0.0 && !self.is_dying() && !self.is_reloading() && self.ammo > 0 && !self.overheated
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.lives == 0
+    }
+
+    // The player is kept out of the top third of the screen, where enemies spawn
+    pub fn play_area(screen: &ScreenConfig) -> graphics::Rect {
+        graphics::Rect::new(0.0, screen.height / 3.0, screen.width, screen.height * 2.0 / 3.0)
+    }
+
+    pub fn update(&mut self, amount: Vector2<f32>, seconds: f32, bounds: graphics::Rect) -> Option<PlayerEvent> {
+        self.invuln_timer = f32::max(self.invuln_timer - seconds, 0.0);
+        self.time_until_next_shot = f32::max(self.time_until_next_shot - seconds, 0.0);
+        self.rapid_fire_timer = f32::max(self.rapid_fire_timer - seconds, 0.0);
+        self.fire_buffer = f32::max(self.fire_buffer - seconds, 0.0);
+        self.cool_down(seconds);
+        if self.is_reloading() {
+            self.reload_timer = f32::max(self.reload_timer - seconds, 0.0);
+            if self.reload_timer <= 0.0 {
+                self.ammo = Self::MAG_SIZE;
+            }
+        }
+
+        if matches!(self.state, PlayerState::Shooting) && self.shoot_anim_timer > 0.0 {
+            self.shoot_anim_timer = f32::max(self.shoot_anim_timer - seconds, 0.0);
+            if self.shoot_anim_timer <= 0.0 {
+                self.state = PlayerState::Normal;
+            }
+        }
+
+        // Input is ignored until the death animation finishes and we respawn
+        if self.is_dying() {
+            self.dying_timer = f32::max(self.dying_timer - seconds, 0.0);
+            if self.dying_timer <= 0.0 {
+                self.respawn();
+                return Some(PlayerEvent::Respawned);
+            }
+            return None;
+        }
+
+        // Normalize diagonal input so it isn't faster than moving along a single axis
+        let input_length = length(amount);
+        let direction = if input_length > 1.0 {
+            Vector2 { x: amount.x / input_length, y: amount.y / input_length }
+        } else {
+            amount
+        };
+
+        if input_length > 0.0 {
+            let speed = self.tuning.player_speed;
+            let target = Vector2 { x: direction.x * speed, y: direction.y * speed };
+            let delta = Vector2 { x: target.x - self.velocity.x, y: target.y - self.velocity.y };
+            let delta_length = length(delta);
+            let max_change = Self::ACCELERATION * seconds;
+
+            if delta_length <= max_change {
+                self.velocity = target;
+            } else {
+                self.velocity.x += delta.x / delta_length * max_change;
+                self.velocity.y += delta.y / delta_length * max_change;
+            }
+        } else {
+            let speed = length(self.velocity);
+            let new_speed = f32::max(speed - Self::FRICTION * seconds, 0.0);
+            if speed > 0.0 {
+                self.velocity.x *= new_speed / speed;
+                self.velocity.y *= new_speed / speed;
+            }
+        }
+
+        let speed = length(self.velocity);
+        let max_speed = self.tuning.player_speed;
+        if speed > max_speed {
+            self.velocity.x *= max_speed / speed;
+            self.velocity.y *= max_speed / speed;
+        }
+
+        let new_x = self.pos.x + self.velocity.x * seconds;
+        let new_y = self.pos.y + self.velocity.y * seconds;
+        self.pos.x = match self.movement_bounds {
+            MovementBounds::Clamp => f32::clamp(new_x, bounds.left(), bounds.right()),
+            MovementBounds::Wrap => Self::wrap_x(new_x, bounds),
+        };
+        self.pos.y = f32::clamp(new_y, bounds.top(), bounds.bottom());
+
+        // Stop dead against the edges instead of sticking to them
+        if self.pos.x != new_x && self.movement_bounds == MovementBounds::Clamp { self.velocity.x = 0.0; }
+        if self.pos.y != new_y { self.velocity.y = 0.0; }
+
+        // Bank into horizontal movement, leveling out again when we stop
+        let target_tilt = f32::clamp(self.velocity.x / self.tuning.player_speed.max(1.0), -1.0, 1.0) * Self::MAX_TILT;
+        self.tilt += (target_tilt - self.tilt) * f32::min(Self::TILT_RATE * seconds, 1.0);
+
+        None
+    }
+
+    // Holding fire charges the shot, which goes off when fire is released. With autofire on,
+    // holding fire shoots uncharged shots as fast as the cooldown allows instead.
+    pub fn update_weapon(&mut self, fire: bool, seconds: f32) -> Vec<PlayerEvent> {
+        let was_held = self.fire_held;
+        self.fire_held = fire;
+
+        if self.is_dying() {
+            // Can't shoot until we've respawned
+            self.fire_buffer = 0.0;
+            Vec::new()
+        } else if fire && self.autofire {
+            // Held fire keeps shooting as often as the cooldown allows, so there's no need to
+            // buffer the frames in between
+            let shots = self.shoot();
+            self.fire_buffer = 0.0;
+            shots.into_iter().map(PlayerEvent::FiredShot).collect()
+        } else if fire {
+            self.charge_up(seconds);
+            self.state = PlayerState::Shooting;
+            Vec::new()
+        } else if !self.autofire && (was_held || self.fire_buffer > 0.0) && self.can_shoot() {
+            self.fire_buffer = 0.0;
+            self.shoot().into_iter().map(PlayerEvent::FiredShot).collect()
+        } else {
+            // Let go while still cooling down, so nothing was fired yet
+            if was_held {
+                if !self.autofire {
+                    self.buffer_fire();
+                }
+                self.state = PlayerState::Normal;
+            }
+            Vec::new()
         }
     }
 
-    pub fn update(&mut self, amount: f32, seconds: f32, max_right: f32) {
-        let new_pos = self.pos.x + Self::SPEED * seconds * amount;
-        self.pos.x = f32::clamp(new_pos, 0.0, max_right);
+    // Wraps once the whole ship has left one side, bringing it back in just beyond the other side,
+    // so it slides back onto the screen instead of popping in half visible
+    fn wrap_x(x: f32, bounds: graphics::Rect) -> f32 {
+        let half_width = Self::WIDTH / 2.0;
+
+        if x - half_width > bounds.right() {
+            bounds.left() - half_width
+        } else if x + half_width < bounds.left() {
+            bounds.right() + half_width
+        } else {
+            x
+        }
+    }
+
+    fn respawn(&mut self) {
+        self.state = PlayerState::Normal;
+        self.pos = self.spawn_pos;
+        self.velocity = Vector2 { x: 0.0, y: 0.0 };
+        self.tilt = 0.0;
+        self.invuln_timer = Self::INVULN_TIME;
+    }
+
+    // Which of the skin's images shows for the current state. Dying shrinks the normal one.
+    pub fn current_image<'a, T>(&self, images: &'a SkinImages<T>) -> &'a T {
+        match self.state {
+            PlayerState::Shooting => &images.shooting,
+            PlayerState::Normal | PlayerState::Dying => &images.normal,
+        }
     }
 
     pub fn draw(&self, canvas: &mut graphics::Canvas, assets: &Assets) {
+        let image = self.current_image(assets.skin_images(self.skin));
         match self.state {
             PlayerState::Normal => {
                 let draw_params = graphics::DrawParam::default().
                     dest(self.pos).
+                    rotation(self.tilt).
                     scale(Vector2 { x: 0.95, y: 0.95 }).
                     offset(Point2 { x: 0.5, y: 1.0 });
-                canvas.draw(&assets.ferris_normal_image, draw_params);
+                canvas.draw(image, draw_params);
             },
 
             PlayerState::Shooting => {
                 let draw_params = graphics::DrawParam::default().
                     dest(self.pos).
+                    rotation(self.tilt).
                     offset(Point2 { x: 0.545, y: 0.96 });
-                canvas.draw(&assets.ferris_shooting_image, draw_params);
+                canvas.draw(image, draw_params);
+            },
+
+            // Shrinks and fades out over the death animation
+            PlayerState::Dying => {
+                let progress = self.dying_timer / Self::DEATH_TIME;
+                let draw_params = graphics::DrawParam::default().
+                    dest(self.pos).
+                    scale(Vector2 { x: 0.95 * progress, y: 0.95 * progress }).
+                    offset(Point2 { x: 0.5, y: 1.0 }).
+                    color(graphics::Color::new(1.0, 1.0, 1.0, progress));
+                canvas.draw(image, draw_params);
             },
         }
     }
@@ -58,67 +557,1154 @@ impl Player {
 pub struct Shot {
     pub pos: Point2<f32>,
     pub is_alive: bool,
+    pub damage: u32,
+    pub scale: f32,
+    pub pierce_count: u32,
+    pub homing: bool,
+    // Whether this enemy shot has already earned the player graze points
+    pub grazed: bool,
     velocity: Vector2<f32>,
+    struck: Vec<u64>,
+    // Ring buffer of the last few positions, `trail_start` being the oldest
+    trail: [Point2<f32>; Shot::TRAIL_LENGTH],
+    trail_start: usize,
+    trail_len: usize,
 }
 
 impl Shot {
-    pub fn new(pos: Point2<f32>) -> Self {
+    pub const COLLISION_SIZE: f32 = 16.0;
+    pub const SPRITE_HEIGHT: f32 = 16.0;
+    pub const SPEED: f32 = 500.0;
+    pub const ENEMY_SPEED: f32 = 300.0;
+    // Radians per second
+    pub const MAX_TURN_RATE: f32 = 3.0;
+    pub const TRAIL_LENGTH: usize = 4;
+    pub const CANCEL_MIN_DAMAGE: u32 = 1;
+    pub const TRAIL_ALPHA: f32 = 0.5;
+
+    pub fn new(pos: Point2<f32>, velocity: Vector2<f32>) -> Self {
         Shot {
-            pos,
+            pos, velocity,
             is_alive: true,
-            velocity: Vector2 { x: 0.0, y: -500.0 },
+            damage: 1,
+            scale: 1.0,
+            pierce_count: 0,
+            homing: false,
+            grazed: false,
+            struck: Vec::new(),
+            trail: [pos; Self::TRAIL_LENGTH],
+            trail_start: 0,
+            trail_len: 0,
+        }
+    }
+
+    pub fn with_power(mut self, damage: u32, scale: f32) -> Self {
+        self.damage = damage;
+        self.scale = scale;
+        self
+    }
+
+    // The shot goes through `pierce_count` enemies and dies on the next one
+    pub fn with_pierce(mut self, pierce_count: u32) -> Self {
+        self.pierce_count = pierce_count;
+        self
+    }
+
+    pub fn with_homing(mut self) -> Self {
+        self.homing = true;
+        self
+    }
+
+    // Velocity for a shot angled `angle` radians off straight up, positive angles going right
+    pub fn direction_velocity(angle: f32) -> Vector2<f32> {
+        Self::direction_velocity_at(angle, Self::SPEED)
+    }
+
+    pub fn direction_velocity_at(angle: f32, speed: f32) -> Vector2<f32> {
+        Vector2 {
+            x: speed * angle.sin(),
+            y: -speed * angle.cos(),
         }
     }
 
+    pub fn velocity(&self) -> Vector2<f32> {
+        self.velocity
+    }
+
+    // A fan of enemy shots aimed down, centered on straight down
+    pub fn volley(muzzle: Point2<f32>, count: usize, spread: f32) -> Vec<Shot> {
+        (0..count).map(|i| {
+            let angle = (i as f32 - (count as f32 - 1.0) / 2.0) * spread;
+            let velocity = Vector2 {
+                x: Shot::ENEMY_SPEED * angle.sin(),
+                y: Shot::ENEMY_SPEED * angle.cos(),
+            };
+            Shot::new(muzzle, velocity)
+        }).collect()
+    }
+
+    // Past positions, oldest first
+    pub fn trail(&self) -> impl Iterator<Item = Point2<f32>> + '_ {
+        (0..self.trail_len).map(move |i| self.trail[(self.trail_start + i) % Self::TRAIL_LENGTH])
+    }
+
+    // Where the shot was on the previous update
+    pub fn previous_pos(&self) -> Option<Point2<f32>> {
+        self.trail().last()
+    }
+
+    // Covers everything between the previous and current position, for finding what the shot may
+    // have passed through
+    pub fn swept_rect(&self, prev_pos: Point2<f32>) -> graphics::Rect {
+        let rect = self.bounding_rect();
+        let size = rect.w;
+        rect.combine_with(graphics::Rect::new(prev_pos.x, prev_pos.y, size, size))
+    }
+
+    fn record_trail(&mut self) {
+        let end = (self.trail_start + self.trail_len) % Self::TRAIL_LENGTH;
+        self.trail[end] = self.pos;
+
+        if self.trail_len < Self::TRAIL_LENGTH {
+            self.trail_len += 1;
+        } else {
+            self.trail_start = (self.trail_start + 1) % Self::TRAIL_LENGTH;
+        }
+    }
+
+    // Turns a homing shot toward the nearest live enemy, at most `MAX_TURN_RATE` radians per
+    // second. The speed stays the same.
+    pub fn home_toward(&mut self, targets: &[Enemy], seconds: f32) {
+        if !self.homing {
+            return;
+        }
+
+        let rect = self.bounding_rect();
+        let center = Point2 { x: rect.x + rect.w / 2.0, y: rect.y + rect.h / 2.0 };
+        let distance_squared = |target: Point2<f32>| {
+            (target.x - center.x).powi(2) + (target.y - center.y).powi(2)
+        };
+
+        let nearest = targets.iter().
+            filter(|enemy| enemy.is_alive).
+            map(|enemy| enemy.bounding_circle().0).
+            min_by(|a, b| distance_squared(*a).total_cmp(&distance_squared(*b)));
+        let target = match nearest {
+            Some(target) => target,
+            None => return,
+        };
+
+        let current = f32::atan2(self.velocity.y, self.velocity.x);
+        let desired = f32::atan2(target.y - center.y, target.x - center.x);
+
+        // Take the short way round
+        let mut turn = desired - current;
+        if turn > PI { turn -= 2.0 * PI; }
+        if turn < -PI { turn += 2.0 * PI; }
+
+        let max_turn = Self::MAX_TURN_RATE * seconds;
+        let angle = current + f32::clamp(turn, -max_turn, max_turn);
+        let speed = length(self.velocity);
+        self.velocity = Vector2 { x: speed * angle.cos(), y: speed * angle.sin() };
+    }
+
     pub fn update(&mut self, seconds: f32) {
+        self.record_trail();
         self.pos.x += self.velocity.x * seconds;
         self.pos.y += self.velocity.y * seconds;
+
+        // Only despawn once the whole sprite has left the top of the screen
+        if self.pos.y < -Self::SPRITE_HEIGHT {
+            self.is_alive = false;
+        }
+    }
+
+    // Enemy shots fall down instead, so they need to know where the bottom is
+    pub fn update_with_bounds(&mut self, seconds: f32, screen_height: f32) {
+        self.update(seconds);
+
+        if self.pos.y > screen_height {
+            self.is_alive = false;
+        }
+    }
+
+    pub fn collides_with_player(&self, player: &Player) -> bool {
+        collides(self, player)
+    }
+
+    // Catches enemies the shot jumped over between frames. Moving the shot's rect along the segment
+    // is the same as moving its corner through the enemy's rect grown by the shot's size.
+    pub fn swept_collides(&self, prev_pos: Point2<f32>, enemy: &Enemy) -> bool {
+        let size = Self::COLLISION_SIZE * self.scale;
+        let rect = enemy.bounding_rect();
+        let grown = graphics::Rect::new(rect.x - size, rect.y - size, rect.w + size, rect.h + size);
+
+        segment_intersects_rect(prev_pos, self.pos, grown)
+    }
+
+    // Checks against whichever collision shape the enemy uses
+    pub fn hits(&self, enemy: &Enemy) -> bool {
+        match enemy.collision_shape {
+            CollisionShape::Rect => self.collides_with(enemy),
+            CollisionShape::Circle => self.collides_with_circle(enemy),
+        }
+    }
+
+    // Damages the enemy if the shot hits it and hasn't already gone through it. Returns whether the
+    // enemy was destroyed, or None if there was no hit.
+    pub fn strike(&mut self, enemy: &mut Enemy) -> Option<bool> {
+        if !self.is_alive || !enemy.is_alive || self.struck.contains(&enemy.id) {
+            return None;
+        }
+
+        let swept = self.previous_pos().is_some_and(|prev_pos| self.swept_collides(prev_pos, enemy));
+        if !self.hits(enemy) && !swept {
+            return None;
+        }
+
+        if self.pierce_count > 0 {
+            self.pierce_count -= 1;
+            self.struck.push(enemy.id);
+        } else {
+            self.is_alive = false;
+        }
+
+        Some(enemy.hit(self.damage))
+    }
+
+    pub fn collides_with(&self, enemy: &Enemy) -> bool {
+        collides(self, enemy)
+    }
+
+    pub fn collides_with_circle(&self, enemy: &Enemy) -> bool {
+        let (center, radius) = enemy.bounding_circle();
+        let rect = self.bounding_rect();
+
+        // Distance from the circle's center to the closest point of the shot
+        let closest_x = f32::clamp(center.x, rect.left(), rect.right());
+        let closest_y = f32::clamp(center.y, rect.top(), rect.bottom());
+        let dx = center.x - closest_x;
+        let dy = center.y - closest_y;
+
+        dx * dx + dy * dy <= radius * radius
     }
 
     pub fn draw(&mut self, canvas: &mut graphics::Canvas, assets: &Assets) {
-        canvas.draw(&assets.shot_image, graphics::DrawParam::default().dest(self.pos));
+        let scale = Vector2 { x: self.scale, y: self.scale };
+
+        // Older copies fade out more
+        for (i, pos) in self.trail().enumerate() {
+            let alpha = Self::TRAIL_ALPHA * (i + 1) as f32 / (self.trail_len + 1) as f32;
+            let draw_params = graphics::DrawParam::default().
+                dest(pos).
+                scale(scale).
+                color(graphics::Color::new(1.0, 1.0, 1.0, alpha));
+            canvas.draw(&assets.shot_image, draw_params);
+        }
+
+        let draw_params = graphics::DrawParam::default().
+            dest(self.pos).
+            scale(scale);
+        canvas.draw(&assets.shot_image, draw_params);
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerupKind {
+    RapidFire,
+    Spread,
+    ExtraLife,
+    Piercing,
+    Bomb,
+    // Slows down every enemy on screen, which is up to whoever collects it to apply
+    Freeze,
+}
+
+#[derive(Debug, Clone)]
+pub struct Powerup {
+    pub pos: Point2<f32>,
+    pub velocity: Vector2<f32>,
+    pub is_alive: bool,
+    pub kind: PowerupKind,
+}
+
+impl Powerup {
+    pub const SIZE: f32 = 30.0;
+    pub const FALL_SPEED: f32 = 150.0;
+    pub const RAPID_FIRE_DURATION: f32 = 8.0;
+    pub const PIERCE_UPGRADE: u32 = 2;
+    pub const FREEZE_DURATION: f32 = 4.0;
+    pub const FREEZE_FACTOR: f32 = 0.4;
+
+    // `pos` is the top-left corner, same as enemies
+    pub fn new(pos: Point2<f32>, kind: PowerupKind) -> Self {
+        Powerup {
+            pos, kind,
+            velocity: Vector2 { x: 0.0, y: Self::FALL_SPEED },
+            is_alive: true,
+        }
+    }
+
+    pub fn update(&mut self, seconds: f32, screen_height: f32) {
+        self.pos.x += self.velocity.x * seconds;
+        self.pos.y += self.velocity.y * seconds;
+
+        if self.pos.y > screen_height {
+            self.is_alive = false;
+        }
+    }
+
+    pub fn collides_with_player(&self, player: &Player) -> bool {
+        collides(self, player)
+    }
+
+    // Returns true if the player picked it up. A powerup can only be picked up once.
+    pub fn collect(&mut self, player: &mut Player) -> bool {
+        if !self.is_alive || !self.collides_with_player(player) {
+            return false;
+        }
+
+        self.is_alive = false;
+        match self.kind {
+            PowerupKind::RapidFire => player.rapid_fire_timer = Self::RAPID_FIRE_DURATION,
+            PowerupKind::Spread => player.weapon_mode = WeaponMode::Spread,
+            PowerupKind::ExtraLife => player.lives += 1,
+            PowerupKind::Piercing => player.pierce_count = Self::PIERCE_UPGRADE,
+            PowerupKind::Bomb => player.bombs += 1,
+            PowerupKind::Freeze => (), // Do nothing
+        }
+        true
+    }
+
+    pub fn color(&self) -> graphics::Color {
+        match self.kind {
+            PowerupKind::RapidFire => graphics::Color::YELLOW,
+            PowerupKind::Spread => graphics::Color::CYAN,
+            PowerupKind::ExtraLife => graphics::Color::GREEN,
+            PowerupKind::Piercing => graphics::Color::MAGENTA,
+            PowerupKind::Bomb => graphics::Color::RED,
+            PowerupKind::Freeze => graphics::Color::WHITE,
+        }
+    }
+
+    pub fn draw(&self, canvas: &mut graphics::Canvas) {
+        let draw_params = graphics::DrawParam::default().
+            dest_rect(self.bounding_rect()).
+            color(self.color());
+        canvas.draw(&graphics::Quad, draw_params);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnemyKind {
+    Grunt,
+    Tank,
+    Fast,
+    Boss,
+    Splitter,
+    // What a splitter breaks into
+    Shard,
+}
+
+// Drawn on top of each enemy so its kind can be told apart without relying on color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkerShape {
+    Circle,
+    Square,
+    Triangle,
+    Diamond,
+    Cross,
+    Dot,
+}
+
+impl MarkerShape {
+    pub const SIZE: f32 = 12.0;
+    pub const COLOR: graphics::Color = graphics::Color::WHITE;
+
+    // Centered on `center`, fitting in a `SIZE` square
+    pub fn draw(self, center: Point2<f32>, canvas: &mut graphics::Canvas, ctx: &mut Context) -> GameResult<()> {
+        let half = Self::SIZE / 2.0;
+        let stroke = graphics::DrawMode::Stroke(graphics::StrokeOptions::default().with_line_width(2.0));
+        let point = |x: f32, y: f32| Point2 { x: center.x + x, y: center.y + y };
+
+        let mut mesh_builder = graphics::MeshBuilder::new();
+        match self {
+            MarkerShape::Circle => mesh_builder.circle(stroke, center, half, 0.5, Self::COLOR)?,
+            MarkerShape::Dot => mesh_builder.circle(graphics::DrawMode::fill(), center, half / 2.0, 0.5, Self::COLOR)?,
+            MarkerShape::Square => {
+                let rect = graphics::Rect::new(center.x - half, center.y - half, Self::SIZE, Self::SIZE);
+                mesh_builder.rectangle(stroke, rect, Self::COLOR)?
+            },
+            MarkerShape::Triangle => {
+                mesh_builder.polygon(stroke, &[point(0.0, -half), point(half, half), point(-half, half)], Self::COLOR)?
+            },
+            MarkerShape::Diamond => {
+                mesh_builder.polygon(stroke, &[point(0.0, -half), point(half, 0.0), point(0.0, half), point(-half, 0.0)], Self::COLOR)?
+            },
+            MarkerShape::Cross => {
+                mesh_builder.line(&[point(-half, -half), point(half, half)], 2.0, Self::COLOR)?;
+                mesh_builder.line(&[point(half, -half), point(-half, half)], 2.0, Self::COLOR)?
+            },
+        };
+
+        let mesh = graphics::Mesh::from_data(ctx, mesh_builder.build());
+        canvas.draw(&mesh, graphics::DrawParam::default());
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnemyStats {
+    pub label: &'static str,
+    pub health: u32,
+    pub speed: f32,
+    pub point_value: u32,
+}
+
+impl EnemyKind {
+    pub const ALL: [EnemyKind; 6] = [
+        EnemyKind::Grunt, EnemyKind::Tank, EnemyKind::Fast, EnemyKind::Boss,
+        EnemyKind::Splitter, EnemyKind::Shard,
+    ];
+
+    pub fn stats(self) -> EnemyStats {
+        match self {
+            EnemyKind::Grunt => EnemyStats { label: "NULLs",               health: 1,  speed: 120.0, point_value: 1 },
+            EnemyKind::Tank  => EnemyStats { label: "Bloat",               health: 5,  speed: 60.0,  point_value: 5 },
+            EnemyKind::Fast  => EnemyStats { label: "Data Races",          health: 1,  speed: 250.0, point_value: 3 },
+            EnemyKind::Boss  => EnemyStats { label: "Undefined Behaviour", health: 30, speed: 30.0,  point_value: 50 },
+            EnemyKind::Splitter => EnemyStats { label: "Forks",            health: 2,  speed: 80.0,  point_value: 4 },
+            EnemyKind::Shard => EnemyStats { label: "fork",                health: 1,  speed: 200.0, point_value: 1 },
+        }
+    }
+
+    pub fn marker(self) -> MarkerShape {
+        match self {
+            EnemyKind::Grunt    => MarkerShape::Circle,
+            EnemyKind::Tank     => MarkerShape::Square,
+            EnemyKind::Fast     => MarkerShape::Triangle,
+            EnemyKind::Boss     => MarkerShape::Diamond,
+            EnemyKind::Splitter => MarkerShape::Cross,
+            EnemyKind::Shard    => MarkerShape::Dot,
+        }
+    }
+
+    pub fn spawn(self, pos: Point2<f32>, ctx: &mut Context) -> GameResult<Enemy> {
+        let stats = self.stats();
+        let sprite = Box::new(TextSprite::new(stats.label, Assets::MAIN_FONT, ctx)?.with_shadow());
+        let enemy = self.build(stats.label, pos, stats.speed, sprite)?.with_entry_duration(Enemy::ENTRY_DURATION);
+        self.with_abilities(enemy, ctx)
+    }
+
+    // Adds each kind's special attacks. Some need the graphics context to set up, like a splitter's
+    // children.
+    pub fn with_abilities(self, enemy: Enemy, ctx: &mut Context) -> GameResult<Enemy> {
+        match self {
+            EnemyKind::Splitter => {
+                let split = Split::text(enemy.label(), ctx)?;
+                Ok(enemy.with_split(split))
+            },
+            EnemyKind::Tank => Ok(enemy.with_laser(Laser::INTERVAL)),
+            _ => Ok(enemy),
+        }
+    }
+
+    // Uses this kind's health and points with any label, speed, and sprite
+    pub fn build(self, label: &str, pos: Point2<f32>, speed: f32, sprite: Box<dyn Sprite>) -> GameResult<Enemy> {
+        let stats = self.stats();
+        Ok(Enemy::new(label, pos, speed, stats.health, stats.point_value, sprite)?.with_kind(self))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaserPhase {
+    Telegraph,
+    Active,
+    Done,
+}
+
+// A beam fired straight down from an enemy. It first shows a thin warning line for the telegraph
+// time, then fires the full beam for the active time, hurting the player whenever they're under it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Laser {
+    // Center of the column the beam covers
+    pub x: f32,
+    pub telegraph_timer: f32,
+    pub active_timer: f32,
+}
+
+impl Laser {
+    pub const TELEGRAPH_TIME: f32 = 1.0;
+    pub const ACTIVE_TIME: f32 = 0.6;
+    // Time between the end of one laser and the start of the next one's telegraph
+    pub const INTERVAL: f32 = 4.0;
+    pub const WIDTH: f32 = 40.0;
+    pub const TELEGRAPH_WIDTH: f32 = 2.0;
+    pub const DAMAGE: i32 = 1;
+    pub const TELEGRAPH_COLOR: graphics::Color = graphics::Color::new(1.0, 0.2, 0.2, 0.6);
+    pub const BEAM_COLOR: graphics::Color = graphics::Color::new(1.0, 0.4, 0.3, 0.85);
+
+    pub fn new(x: f32, telegraph_time: f32, active_time: f32) -> Self {
+        Laser { x, telegraph_timer: telegraph_time, active_timer: active_time }
+    }
+
+    pub fn phase(&self) -> LaserPhase {
+        if self.telegraph_timer > 0.0 {
+            LaserPhase::Telegraph
+        } else if self.active_timer > 0.0 {
+            LaserPhase::Active
+        } else {
+            LaserPhase::Done
+        }
+    }
+
+    // Time left over from the telegraph carries on into the beam
+    pub fn update(&mut self, seconds: f32) {
+        let mut remaining = seconds;
+        if self.telegraph_timer > 0.0 {
+            let used = f32::min(self.telegraph_timer, remaining);
+            self.telegraph_timer -= used;
+            remaining -= used;
+        }
+        self.active_timer = f32::max(self.active_timer - remaining, 0.0);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.phase() == LaserPhase::Active
+    }
+
+    // Only the full beam does damage, the warning line is harmless
+    pub fn hits_player(&self, player: &Player) -> bool {
+        if !self.is_active() {
+            return false;
+        }
+
+        let rect = player.bounding_rect();
+        rect.left() < self.x + Self::WIDTH / 2.0 && self.x - Self::WIDTH / 2.0 < rect.right()
+    }
+
+    // From `top` down to the bottom of the screen
+    pub fn draw(&self, top: f32, screen_height: f32, canvas: &mut graphics::Canvas) {
+        let (width, color) = match self.phase() {
+            LaserPhase::Telegraph => (Self::TELEGRAPH_WIDTH, Self::TELEGRAPH_COLOR),
+            LaserPhase::Active => (Self::WIDTH, Self::BEAM_COLOR),
+            LaserPhase::Done => return,
+        };
+
+        let rect = graphics::Rect::new(self.x - width / 2.0, top, width, f32::max(screen_height - top, 0.0));
+        canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(rect).color(color));
+    }
+}
+
+// What an enemy breaks into when it dies. The children fan out downwards and don't split again.
+pub struct Split {
+    pub count: u32,
+    pub child_speed: f32,
+    child_sprite: Rc<dyn Fn() -> Box<dyn Sprite>>,
+}
+
+impl Split {
+    pub const COUNT: u32 = 2;
+    pub const CHILD_SPEED: f32 = 200.0;
+    // Radians either side of straight down for the outermost children
+    pub const SPREAD: f32 = 0.6;
+    pub const CHILD_TEXT_SCALE: f32 = 20.0;
+
+    pub fn new(count: u32, child_speed: f32, child_sprite: impl Fn() -> Box<dyn Sprite> + 'static) -> Self {
+        Split { count, child_speed, child_sprite: Rc::new(child_sprite) }
+    }
+
+    // Children showing `label` in smaller text
+    pub fn text(label: &str, ctx: &mut Context) -> GameResult<Split> {
+        let sprite = TextSprite::with_scale(label, Assets::MAIN_FONT, Self::CHILD_TEXT_SCALE, ctx)?.with_shadow();
+        Ok(Split::new(Self::COUNT, Self::CHILD_SPEED, move || Box::new(sprite.clone())))
+    }
+
+    fn child_velocity(&self, index: u32) -> Vector2<f32> {
+        let angle = if self.count > 1 {
+            -Self::SPREAD + 2.0 * Self::SPREAD * index as f32 / (self.count - 1) as f32
+        } else {
+            0.0
+        };
+
+        Vector2 { x: self.child_speed * angle.sin(), y: self.child_speed * angle.cos() }
+    }
+}
+
+impl std::fmt::Debug for Split {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Split").
+            field("count", &self.count).
+            field("child_speed", &self.child_speed).
+            finish_non_exhaustive()
+    }
+}
+
+// Player shots destroy the enemy shots they run into. Both go down in the collision, except that a
+// piercing shot spends one pierce and keeps going, the same as when it goes through an enemy.
+// Shots weaker than `Shot::CANCEL_MIN_DAMAGE` pass by without touching enemy fire. Returns how
+// many enemy shots were destroyed.
+pub fn cancel_shots<'a>(player_shots: impl Iterator<Item = &'a mut Shot>, enemy_shots: &mut [Shot]) -> u32 {
+    let mut cancelled = 0;
+
+    for shot in player_shots.filter(|shot| shot.damage >= Shot::CANCEL_MIN_DAMAGE) {
+        for enemy_shot in enemy_shots.iter_mut() {
+            if !shot.is_alive {
+                break;
+            }
+            if !enemy_shot.is_alive || !collides(shot, enemy_shot) {
+                continue;
+            }
+
+            enemy_shot.is_alive = false;
+            cancelled += 1;
+            if shot.pierce_count > 0 {
+                shot.pierce_count -= 1;
+            } else {
+                shot.is_alive = false;
+            }
+        }
+    }
+
+    cancelled
+}
+
+// Destroys every live enemy and enemy shot. Bombed enemies are worth `points_fraction` of their
+// usual points, rounded down. Returns the points to award.
+pub fn detonate_bomb(enemies: &mut [Enemy], enemy_shots: &mut [Shot], points_fraction: f32) -> u32 {
+    for shot in enemy_shots.iter_mut() {
+        shot.is_alive = false;
+    }
+
+    enemies.iter_mut().
+        filter(|enemy| enemy.is_alive).
+        map(|enemy| {
+            enemy.is_alive = false;
+            (enemy.point_value as f32 * points_fraction) as u32
+        }).
+        sum()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionShape {
+    Rect,
+    Circle,
+}
+
 #[derive(Debug)]
 pub struct Enemy {
     pub pos: Point2<f32>,
     pub is_alive: bool,
+    pub health: u32,
+    pub point_value: u32,
+    pub collision_shape: CollisionShape,
+    pub hit_flash_timer: f32,
+    id: u64,
+    max_health: u32,
     label: String,
     velocity: Vector2<f32>,
     sprite: Box<dyn Sprite>,
+    movement: Box<dyn MovementPattern>,
+    elapsed: f32,
+    fire_interval: Option<f32>,
+    shoot_timer: f32,
+    knockback: Vector2<f32>,
+    knockback_timer: f32,
+    knockback_decay: f32,
+    split: Option<Split>,
+    target: Option<Point2<f32>>,
+    kind: EnemyKind,
+    entry_timer: f32,
+    entry_duration: f32,
+    laser: Option<Laser>,
+    laser_interval: Option<f32>,
+    laser_timer: f32,
+    slow_timer: f32,
+    slow_factor: f32,
+    timeline: Option<Timeline>,
+    // Flipped each time the enemy bounces off a wall, so the pattern swings the other way
+    offset_sign: f32,
 }
 
 impl Enemy {
-    pub fn new(label: &str, pos: Point2<f32>, speed: f32, sprite: Box<dyn Sprite>) -> GameResult<Self> {
+    pub const HIT_FLASH_TIME: f32 = 0.15;
+    pub const HIT_FLASH_COLOR: graphics::Color = graphics::Color::new(1.0, 0.3, 0.3, 1.0);
+    pub const KNOCKBACK_IMPULSE: f32 = 150.0;
+    pub const KNOCKBACK_DECAY: f32 = 0.2;
+    pub const RAM_DAMAGE: i32 = 1;
+    pub const HIGHLIGHT_COLOR: graphics::Color = graphics::Color::new(1.0, 0.9, 0.2, 1.0);
+    pub const SHADOW_OFFSET: Vector2<f32> = Vector2 { x: 6.0, y: 8.0 };
+    pub const SHADOW_COLOR: graphics::Color = graphics::Color::new(0.0, 0.0, 0.0, 0.4);
+    pub const HEALTH_BAR_HEIGHT: f32 = 4.0;
+    // Gap between the bar and the top of the sprite
+    pub const HEALTH_BAR_GAP: f32 = 3.0;
+    pub const HEALTH_BAR_COLOR: graphics::Color = graphics::Color::new(0.2, 0.9, 0.3, 1.0);
+    pub const HEALTH_BAR_BACKGROUND_COLOR: graphics::Color = graphics::Color::new(0.6, 0.1, 0.1, 0.8);
+    // How long spawned enemies take to get up to speed
+    pub const ENTRY_DURATION: f32 = 0.5;
+
+    pub fn new(label: &str, pos: Point2<f32>, speed: f32, health: u32, point_value: u32, sprite: Box<dyn Sprite>) -> GameResult<Self> {
         let label = String::from(label);
 
         Ok(Enemy {
-            pos, label, sprite,
+            pos, label, sprite, health, point_value,
+            max_health: health,
             is_alive: true,
+            collision_shape: CollisionShape::Rect,
+            hit_flash_timer: 0.0,
+            id: NEXT_ENEMY_ID.fetch_add(1, Ordering::Relaxed),
             velocity: Vector2 { x: 0.0, y: speed },
+            movement: Box::new(StraightDown),
+            elapsed: 0.0,
+            fire_interval: None,
+            shoot_timer: 0.0,
+            knockback: Vector2 { x: 0.0, y: 0.0 },
+            knockback_timer: 0.0,
+            knockback_decay: Self::KNOCKBACK_DECAY,
+            split: None,
+            target: None,
+            kind: EnemyKind::Grunt,
+            entry_timer: 0.0,
+            entry_duration: 0.0,
+            laser: None,
+            laser_interval: None,
+            laser_timer: 0.0,
+            slow_timer: 0.0,
+            slow_factor: 1.0,
+            timeline: None,
+            offset_sign: 1.0,
         })
     }
 
+    // For enemies that start out already damaged, like ones loaded from a save
+    pub fn with_max_health(mut self, max_health: u32) -> Self {
+        self.max_health = u32::max(max_health, self.health);
+        self
+    }
+
+    pub fn max_health(&self) -> u32 {
+        self.max_health
+    }
+
+    // Makes the enemy shoot straight down once every `fire_interval` seconds
+    pub fn with_fire_interval(mut self, fire_interval: f32) -> Self {
+        self.fire_interval = Some(fire_interval);
+        self.shoot_timer = fire_interval;
+        self
+    }
+
+    pub fn with_movement(mut self, movement: Box<dyn MovementPattern>) -> Self {
+        self.movement = movement;
+        self
+    }
+
+    // Starts the enemy off still, easing up to its full velocity over `seconds`
+    pub fn with_entry_duration(mut self, seconds: f32) -> Self {
+        self.entry_duration = seconds;
+        self.entry_timer = 0.0;
+        self
+    }
+
+    // Smoothstep from 0 to 1 over the entry duration
+    fn entry_progress(&self) -> f32 {
+        if self.entry_duration <= 0.0 {
+            return 1.0;
+        }
+
+        let t = f32::clamp(self.entry_timer / self.entry_duration, 0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    // Fires a laser every `interval` seconds, counting from the end of the last one
+    pub fn with_laser(mut self, interval: f32) -> Self {
+        self.laser_interval = Some(interval);
+        self.laser_timer = interval;
+        self
+    }
+
+    // Scripted attacks on top of the enemy's usual behaviour
+    pub fn with_timeline(mut self, timeline: Timeline) -> Self {
+        self.timeline = Some(timeline);
+        self
+    }
+
+    pub fn timeline(&self) -> Option<&Timeline> {
+        self.timeline.as_ref()
+    }
+
+    // Runs the actions that came due during this update
+    pub fn update_timeline(&mut self, seconds: f32) -> Vec<EnemyEvent> {
+        let actions = match &mut self.timeline {
+            Some(timeline) => timeline.advance(seconds),
+            None => return Vec::new(),
+        };
+
+        let mut events = Vec::new();
+        for action in actions {
+            match action {
+                Action::FireVolley { count, spread } => {
+                    let rect = self.bounding_rect();
+                    let muzzle = Point2 { x: rect.x + rect.w / 2.0, y: rect.bottom() };
+                    events.extend(Shot::volley(muzzle, count, spread).into_iter().map(EnemyEvent::FiredShot));
+                },
+                Action::ChangePattern(pattern) => self.movement = pattern.build(),
+                Action::Dash(impulse) => self.apply_knockback(impulse),
+            }
+        }
+
+        events
+    }
+
+    pub fn laser(&self) -> Option<&Laser> {
+        self.laser.as_ref()
+    }
+
+    // The beam follows the enemy as it moves
+    pub fn update_laser(&mut self, seconds: f32) {
+        let interval = match self.laser_interval {
+            Some(interval) => interval,
+            None => return,
+        };
+        let center_x = self.bounding_circle().0.x;
+
+        match &mut self.laser {
+            Some(laser) => {
+                laser.update(seconds);
+                laser.x = center_x;
+                if laser.phase() == LaserPhase::Done {
+                    self.laser = None;
+                }
+            },
+            None => {
+                self.laser_timer -= seconds;
+                if self.laser_timer <= 0.0 {
+                    self.laser_timer += interval;
+                    self.laser = Some(Laser::new(center_x, Laser::TELEGRAPH_TIME, Laser::ACTIVE_TIME));
+                }
+            },
+        }
+    }
+
+    pub fn with_kind(mut self, kind: EnemyKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn kind(&self) -> EnemyKind {
+        self.kind
+    }
+
+    pub fn with_split(mut self, split: Split) -> Self {
+        self.split = Some(split);
+        self
+    }
+
+    pub fn with_velocity(mut self, velocity: Vector2<f32>) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    // Where the player is, for movement patterns that chase it. Takes effect on the next update.
+    pub fn track(&mut self, target: Point2<f32>) {
+        self.target = Some(target);
+    }
+
+    // Enemies to add once this one has been destroyed, starting from where it died
+    pub fn on_death(&self) -> Vec<Enemy> {
+        let split = match &self.split {
+            Some(split) => split,
+            None => return Vec::new(),
+        };
+        let (center, _) = self.bounding_circle();
+
+        (0..split.count).
+            filter_map(|index| {
+                let sprite = (split.child_sprite)();
+                let pos = Point2 { x: center.x - sprite.width() / 2.0, y: center.y - sprite.height() / 2.0 };
+                let child = EnemyKind::Shard.build(&self.label, pos, split.child_speed, sprite).ok()?;
+                Some(child.with_velocity(split.child_velocity(index)))
+            }).
+            collect()
+    }
+
+    // How long a knockback takes to wear off
+    pub fn with_knockback_decay(mut self, seconds: f32) -> Self {
+        self.knockback_decay = seconds;
+        self
+    }
+
     pub fn label(&self) -> &str {
         self.label.as_str()
     }
 
+    // Unique among all enemies created while the game is running
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn fire_interval(&self) -> Option<f32> {
+        self.fire_interval
+    }
+
+    // Velocity without knockback
+    pub fn base_velocity(&self) -> Vector2<f32> {
+        self.velocity
+    }
+
+    // Current velocity, eased in while entering and including any knockback that hasn't worn off
+    pub fn velocity(&self) -> Vector2<f32> {
+        let strength = if self.knockback_decay > 0.0 {
+            self.knockback_timer / self.knockback_decay
+        } else {
+            0.0
+        };
+        let entry = self.entry_progress();
+
+        Vector2 {
+            x: self.velocity.x * entry + self.knockback.x * strength,
+            y: self.velocity.y * entry + self.knockback.y * strength,
+        }
+    }
+
+    // The impulse is added on top of the normal velocity and fades out linearly
+    pub fn apply_knockback(&mut self, impulse: Vector2<f32>) {
+        self.knockback = impulse;
+        self.knockback_timer = self.knockback_decay;
+    }
+
+    // Returns whether this hit destroyed the enemy
+    pub fn hit(&mut self, damage: u32) -> bool {
+        if !self.is_alive {
+            return false;
+        }
+
+        self.health = self.health.saturating_sub(damage);
+        if self.health == 0 {
+            self.is_alive = false;
+        } else {
+            self.hit_flash_timer = Self::HIT_FLASH_TIME;
+        }
+
+        !self.is_alive
+    }
+
+    // Moves at `factor` times its usual speed for `duration` seconds, replacing any slow already on
+    pub fn slow(&mut self, factor: f32, duration: f32) {
+        self.slow_factor = factor.clamp(0.0, 1.0);
+        self.slow_timer = duration;
+    }
+
+    pub fn is_slowed(&self) -> bool {
+        self.slow_timer > 0.0
+    }
+
+    fn slow_multiplier(&self) -> f32 {
+        if self.is_slowed() { self.slow_factor } else { 1.0 }
+    }
+
     pub fn update(&mut self, seconds: f32) {
-        self.pos.x += self.velocity.x * seconds;
-        self.pos.y += self.velocity.y * seconds;
+        // Slowing only affects movement, the enemy's timers keep going as usual
+        let moved = seconds * self.slow_multiplier();
+        self.slow_timer = f32::max(self.slow_timer - seconds, 0.0);
+
+        // The position follows an anchor moving with the velocity, displaced by the pattern offset.
+        // Applying the change in offset keeps `pos` the only source of truth for where we are.
+        let old_offset = self.movement.offset(self.elapsed);
+        self.elapsed += moved;
+        let new_offset = self.movement.offset(self.elapsed);
+
+        let steering = match self.target {
+            Some(target) => self.movement.steer(self.bounding_circle().0, target, moved),
+            None => Vector2 { x: 0.0, y: 0.0 },
+        };
+
+        let velocity = self.velocity();
+        self.pos.x += (velocity.x + steering.x) * moved + self.offset_sign * (new_offset.x - old_offset.x);
+        self.pos.y += (velocity.y + steering.y) * moved + new_offset.y - old_offset.y;
+        self.sprite.update(seconds);
+        self.hit_flash_timer = f32::max(self.hit_flash_timer - seconds, 0.0);
+        self.knockback_timer = f32::max(self.knockback_timer - seconds, 0.0);
+        self.entry_timer = f32::min(self.entry_timer + seconds, self.entry_duration);
+    }
+
+    pub fn collides_with_player(&self, player: &Player) -> bool {
+        collides(self, player)
+    }
+
+    // Flying into the player destroys the enemy and damages the player. Returns whether it hit.
+    pub fn ram(&mut self, player: &mut Player) -> bool {
+        if !self.is_alive || player.is_dying() || !self.collides_with_player(player) {
+            return false;
+        }
+
+        self.is_alive = false;
+        player.take_damage(Self::RAM_DAMAGE);
+        true
+    }
+
+    // Returns a shot if the enemy fired during this update
+    pub fn update_shooting(&mut self, seconds: f32) -> Option<Shot> {
+        let fire_interval = self.fire_interval?;
+
+        self.shoot_timer -= seconds;
+        if self.shoot_timer > 0.0 {
+            return None;
+        }
+        self.shoot_timer += fire_interval;
+
+        let rect = self.bounding_rect();
+        let muzzle = Point2 {
+            x: rect.x + (rect.w - Shot::COLLISION_SIZE) / 2.0,
+            y: rect.bottom(),
+        };
+        Some(Shot::new(muzzle, Vector2 { x: 0.0, y: Shot::ENEMY_SPEED }))
+    }
+
+    // Moves, fires and checks for escaping all at once
+    pub fn step(&mut self, seconds: f32, screen_width: f32, screen_height: f32) -> Vec<EnemyEvent> {
+        let mut events = Vec::new();
+
+        if self.update_with_bounds(seconds, screen_width, screen_height) {
+            events.push(EnemyEvent::ReachedBottom);
+        }
+        if let Some(shot) = self.update_shooting(seconds) {
+            events.push(EnemyEvent::FiredShot(shot));
+        }
+        self.update_laser(seconds);
+        events.extend(self.update_timeline(seconds));
+
+        events
+    }
+
+    // Returns whether the enemy escaped off the bottom of the screen this update
+    pub fn update_with_bounds(&mut self, seconds: f32, screen_width: f32, screen_height: f32) -> bool {
+        self.update(seconds);
+        self.bounce_off_walls(screen_width);
+
+        if self.is_alive && self.pos.y >= screen_height {
+            self.is_alive = false;
+            return true;
+        }
+
+        false
+    }
+
+    // Once the edge of the sprite goes past a wall, the enemy is reflected back by however far it
+    // went over and its sideways movement is mirrored. That covers the pattern offset as well as
+    // the velocity, so sine and zigzag enemies swing back off the wall instead of sliding along it.
+    fn bounce_off_walls(&mut self, screen_width: f32) {
+        let max_x = f32::max(screen_width - self.bounding_rect().w, 0.0);
+
+        if self.pos.x < 0.0 {
+            self.pos.x = f32::min(-self.pos.x, max_x);
+            self.velocity.x = self.velocity.x.abs();
+            self.offset_sign = -self.offset_sign;
+        } else if self.pos.x > max_x {
+            self.pos.x = f32::max(2.0 * max_x - self.pos.x, 0.0);
+            self.velocity.x = -self.velocity.x.abs();
+            self.offset_sign = -self.offset_sign;
+        }
+    }
+
+    // Drawn tinted to show that it's being targeted
+    pub fn draw_highlighted(&mut self, canvas: &mut graphics::Canvas) {
+        self.sprite.draw_tinted(self.pos, Self::HIGHLIGHT_COLOR, canvas);
     }
 
     pub fn draw(&mut self, canvas: &mut graphics::Canvas) {
-        self.sprite.draw(self.pos, canvas);
+        if self.hit_flash_timer > 0.0 {
+            self.sprite.draw_tinted(self.pos, Self::HIT_FLASH_COLOR, canvas);
+        } else {
+            self.sprite.draw(self.pos, canvas);
+        }
     }
 
-    pub fn bounding_rect(&self, ctx: &mut Context) -> graphics::Rect {
+    // The bar is as wide as the sprite and empties from the right
+    pub fn health_bar_fill_width(&self) -> f32 {
+        let fraction = self.health as f32 / self.max_health.max(1) as f32;
+        self.bounding_rect().w * fraction.clamp(0.0, 1.0)
+    }
+
+    // Only shows up once the enemy has been hurt
+    pub fn draw_health_bar(&self, canvas: &mut graphics::Canvas) {
+        if self.health >= self.max_health {
+            return;
+        }
+
+        let rect = self.bounding_rect();
+        let top = rect.top() - Self::HEALTH_BAR_GAP - Self::HEALTH_BAR_HEIGHT;
+        let background = graphics::Rect::new(rect.left(), top, rect.w, Self::HEALTH_BAR_HEIGHT);
+        canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(background).color(Self::HEALTH_BAR_BACKGROUND_COLOR));
+
+        let filled = graphics::Rect::new(rect.left(), top, self.health_bar_fill_width(), Self::HEALTH_BAR_HEIGHT);
+        canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(filled).color(Self::HEALTH_BAR_COLOR));
+    }
+
+    // Where the sprite's shadow goes, down and to the right of it
+    pub fn shadow_pos(&self) -> Point2<f32> {
+        Point2 { x: self.pos.x + Self::SHADOW_OFFSET.x, y: self.pos.y + Self::SHADOW_OFFSET.y }
+    }
+
+    // Draw every enemy's shadow before any of the enemies, so no shadow ends up on top of one
+    pub fn draw_shadow(&mut self, canvas: &mut graphics::Canvas) {
+        let pos = self.shadow_pos();
+        self.sprite.draw_tinted(pos, Self::SHADOW_COLOR, canvas);
+    }
+
+    // Just inside the top-right corner of the sprite
+    pub fn marker_center(&self) -> Point2<f32> {
+        let rect = self.bounding_rect();
+        let half = MarkerShape::SIZE / 2.0;
+        Point2 { x: rect.right() - half, y: rect.top() + half }
+    }
+
+    pub fn draw_type_marker(&self, canvas: &mut graphics::Canvas, ctx: &mut Context) -> GameResult<()> {
+        self.kind.marker().draw(self.marker_center(), canvas, ctx)
+    }
+
+    // Center and radius of the largest circle that fits inside the sprite
+    pub fn bounding_circle(&self) -> (Point2<f32>, f32) {
+        let rect = self.bounding_rect();
+        let center = Point2 {
+            x: rect.x + rect.w / 2.0,
+            y: rect.y + rect.h / 2.0,
+        };
+
+        (center, f32::min(rect.w, rect.h) / 2.0)
+    }
+}
+
+impl Collidable for Player {
+    // `pos` is the bottom-middle point of the sprite
+    fn bounding_rect(&self) -> graphics::Rect {
+        graphics::Rect::new(self.pos.x - Self::WIDTH / 2.0, self.pos.y - Self::HEIGHT, Self::WIDTH, Self::HEIGHT)
+    }
+}
+
+impl Collidable for Shot {
+    fn bounding_rect(&self) -> graphics::Rect {
+        let size = Self::COLLISION_SIZE * self.scale;
+        graphics::Rect::new(self.pos.x, self.pos.y, size, size)
+    }
+}
+
+impl Collidable for Powerup {
+    fn bounding_rect(&self) -> graphics::Rect {
+        graphics::Rect::new(self.pos.x, self.pos.y, Self::SIZE, Self::SIZE)
+    }
+}
+
+impl Collidable for Enemy {
+    fn bounding_rect(&self) -> graphics::Rect {
         let left   = self.pos.x;
-        let right  = self.pos.x + self.sprite.width(ctx);
+        let right  = self.pos.x + self.sprite.width();
         let top    = self.pos.y;
-        let bottom = self.pos.y + self.sprite.height(ctx);
+        let bottom = self.pos.y + self.sprite.height();
 
         graphics::Rect::new(left, top, right - left, bottom - top)
     }
 }
+
+fn length(v: Vector2<f32>) -> f32 {
+    f32::sqrt(v.x * v.x + v.y * v.y)
+}
@@ -0,0 +1,75 @@
+use ggez::GameResult;
+use ggez::mint::Point2;
+
+use crate::entities::Enemy;
+use crate::movement::{MovementPattern, StraightDown};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormationPattern {
+    // `count` enemies side by side, going right from the base position
+    Row { count: usize, spacing: f32 },
+    // Leader at the base position, the rest alternating left and right behind it
+    VShape { count: usize, spacing: f32 },
+    // `rows` rows going up from the base position, so they all enter the screen in order
+    Grid { rows: usize, columns: usize, spacing: f32 },
+}
+
+// A group of enemies spawned at once. They share a speed and movement pattern and all start with
+// the same elapsed time, so they stay in formation as they move.
+#[derive(Debug, Clone)]
+pub struct Formation<M: MovementPattern + Clone + 'static = StraightDown> {
+    pub pattern: FormationPattern,
+    pub base: Point2<f32>,
+    movement: M,
+}
+
+impl Formation {
+    pub fn new(pattern: FormationPattern, base: Point2<f32>) -> Self {
+        Formation { pattern, base, movement: StraightDown }
+    }
+
+    pub fn row(count: usize, spacing: f32, base: Point2<f32>) -> Self {
+        Formation::new(FormationPattern::Row { count, spacing }, base)
+    }
+}
+
+impl<M: MovementPattern + Clone + 'static> Formation<M> {
+    pub fn with_movement<N: MovementPattern + Clone + 'static>(self, movement: N) -> Formation<N> {
+        Formation { pattern: self.pattern, base: self.base, movement }
+    }
+
+    pub fn positions(&self) -> Vec<Point2<f32>> {
+        let base = self.base;
+        let at = |dx: f32, dy: f32| Point2 { x: base.x + dx, y: base.y + dy };
+
+        match self.pattern {
+            FormationPattern::Row { count, spacing } => {
+                (0..count).map(|i| at(i as f32 * spacing, 0.0)).collect()
+            },
+
+            FormationPattern::VShape { count, spacing } => {
+                (0..count).map(|i| {
+                    let rank = i.div_ceil(2) as f32;
+                    let side = if i % 2 == 1 { 1.0 } else { -1.0 };
+                    at(side * rank * spacing, -rank * spacing)
+                }).collect()
+            },
+
+            FormationPattern::Grid { rows, columns, spacing } => {
+                (0..rows).flat_map(|row| {
+                    (0..columns).map(move |column| (row, column))
+                }).map(|(row, column)| at(column as f32 * spacing, -(row as f32) * spacing)).collect()
+            },
+        }
+    }
+
+    // `make_enemy` creates the enemy for each position, everything else is filled in here
+    pub fn build<F>(&self, mut make_enemy: F) -> GameResult<Vec<Enemy>>
+        where F: FnMut(Point2<f32>) -> GameResult<Enemy>
+    {
+        self.positions().
+            into_iter().
+            map(|pos| Ok(make_enemy(pos)?.with_movement(Box::new(self.movement.clone())))).
+            collect()
+    }
+}
@@ -0,0 +1,61 @@
+use ggez::{Context, GameError, GameResult};
+use serde::{Deserialize, Serialize};
+
+use crate::assets::read_text_file;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScores {
+    pub entries: Vec<HighScoreEntry>,
+}
+
+impl HighScores {
+    pub const MAX_ENTRIES: usize = 10;
+    const PATH: &'static str = "/highscores.json";
+
+    // A missing file just means nobody has set a high score yet. One that can't be read or parsed
+    // gets a warning, since the next save will replace it.
+    pub fn load(ctx: &Context) -> HighScores {
+        let json = match read_text_file(ctx, Self::PATH) {
+            Some(json) => json,
+            None => return HighScores::default(),
+        };
+
+        serde_json::from_str(&json).unwrap_or_else(|e| {
+            eprintln!("Warning: ignoring {}: {}", Self::PATH, e);
+            HighScores::default()
+        })
+    }
+
+    pub fn save(&self, ctx: &Context) -> GameResult<()> {
+        let file = ctx.fs.create(Self::PATH)?;
+        serde_json::to_writer(file, self).
+            map_err(|e| GameError::FilesystemError(e.to_string()))
+    }
+
+    pub fn best(&self) -> u32 {
+        self.entries.first().map_or(0, |entry| entry.score)
+    }
+
+    pub fn qualifies(&self, score: u32) -> bool {
+        self.entries.len() < Self::MAX_ENTRIES || self.entries.iter().any(|entry| score > entry.score)
+    }
+
+    // Returns whether the score made it onto the table
+    pub fn insert(&mut self, name: &str, score: u32) -> bool {
+        if !self.qualifies(score) {
+            return false;
+        }
+
+        // Ties go after existing entries, so the earlier score keeps its place
+        let index = self.entries.iter().position(|entry| score > entry.score).unwrap_or(self.entries.len());
+        self.entries.insert(index, HighScoreEntry { name: String::from(name), score });
+        self.entries.truncate(Self::MAX_ENTRIES);
+        true
+    }
+}
@@ -0,0 +1,184 @@
+use ggez::{Context, GameResult};
+use ggez::graphics::{self, Drawable};
+use ggez::mint::Point2;
+
+use crate::assets::{Assets, TextSprite};
+use crate::entities::Player;
+use crate::score::{Combo, Score};
+
+pub const MARGIN: f32 = 10.0;
+pub const TEXT_SIZE: f32 = 24.0;
+pub const LINE_HEIGHT: f32 = 30.0;
+pub const HEALTH_BAR_TOP: f32 = 44.0;
+pub const HEALTH_BAR_WIDTH: f32 = 150.0;
+pub const HEALTH_BAR_HEIGHT: f32 = 12.0;
+pub const HEAT_BAR_TOP: f32 = 60.0;
+pub const HEAT_BAR_HEIGHT: f32 = 6.0;
+pub const BOSS_BAR_TOP: f32 = 16.0;
+pub const BOSS_BAR_WIDTH: f32 = 400.0;
+pub const BOSS_BAR_HEIGHT: f32 = 16.0;
+
+const HEALTH_COLOR: graphics::Color = graphics::Color::new(0.2, 0.9, 0.3, 1.0);
+const BOSS_HEALTH_COLOR: graphics::Color = graphics::Color::new(0.9, 0.2, 0.2, 1.0);
+const HEAT_COLOR: graphics::Color = graphics::Color::new(1.0, 0.6, 0.1, 1.0);
+const OVERHEATED_COLOR: graphics::Color = graphics::Color::new(1.0, 0.1, 0.1, 1.0);
+const HEALTH_BACKGROUND_COLOR: graphics::Color = graphics::Color::new(0.0, 0.0, 0.0, 0.5);
+
+// Score in the top-left with the health and heat bars underneath, lives, bombs and ammo in the top-right
+pub fn draw_hud(canvas: &mut graphics::Canvas, ctx: &mut Context, score: &Score, combo: &Combo, player: &Player, screen_width: f32) -> GameResult<()> {
+    let score_text = hud_text(&score_label(score, combo));
+    draw_shadowed(canvas, &score_text, Point2 { x: MARGIN, y: MARGIN });
+
+    let lives_text = hud_text(&format!("Lives: {}", player.lives));
+    let lives_width = lives_text.dimensions(ctx).map(|rect| rect.w).unwrap_or(0.0);
+    let lives_pos = Point2 { x: screen_width - MARGIN - lives_width, y: MARGIN };
+    draw_shadowed(canvas, &lives_text, lives_pos);
+
+    let bombs_text = hud_text(&format!("Bombs: {}", player.bombs));
+    let bombs_width = bombs_text.dimensions(ctx).map(|rect| rect.w).unwrap_or(0.0);
+    let bombs_pos = Point2 { x: screen_width - MARGIN - bombs_width, y: MARGIN + LINE_HEIGHT };
+    draw_shadowed(canvas, &bombs_text, bombs_pos);
+
+    let ammo_text = hud_text(&ammo_label(player));
+    let ammo_width = ammo_text.dimensions(ctx).map(|rect| rect.w).unwrap_or(0.0);
+    let ammo_pos = Point2 { x: screen_width - MARGIN - ammo_width, y: MARGIN + LINE_HEIGHT * 2.0 };
+    draw_shadowed(canvas, &ammo_text, ammo_pos);
+
+    let background = graphics::Rect::new(MARGIN, HEALTH_BAR_TOP, HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT);
+    canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(background).color(HEALTH_BACKGROUND_COLOR));
+
+    let health = graphics::Rect::new(MARGIN, HEALTH_BAR_TOP, health_bar_width(player), HEALTH_BAR_HEIGHT);
+    canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(health).color(HEALTH_COLOR));
+
+    let heat_background = graphics::Rect::new(MARGIN, HEAT_BAR_TOP, HEALTH_BAR_WIDTH, HEAT_BAR_HEIGHT);
+    canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(heat_background).color(HEALTH_BACKGROUND_COLOR));
+
+    let heat_color = if player.overheated { OVERHEATED_COLOR } else { HEAT_COLOR };
+    let heat = graphics::Rect::new(MARGIN, HEAT_BAR_TOP, heat_bar_width(player), HEAT_BAR_HEIGHT);
+    canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(heat).color(heat_color));
+
+    Ok(())
+}
+
+pub fn score_label(score: &Score, combo: &Combo) -> String {
+    if combo.multiplier() > 1 {
+        format!("Score: {}  x{}", score.current, combo.multiplier())
+    } else {
+        format!("Score: {}", score.current)
+    }
+}
+
+pub fn ammo_label(player: &Player) -> String {
+    if player.is_reloading() {
+        String::from("Reloading...")
+    } else {
+        format!("Ammo: {}/{}", player.ammo, Player::MAG_SIZE)
+    }
+}
+
+pub fn health_bar_width(player: &Player) -> f32 {
+    let fraction = player.health.max(0) as f32 / Player::MAX_HEALTH as f32;
+    HEALTH_BAR_WIDTH * fraction
+}
+
+pub fn heat_bar_width(player: &Player) -> f32 {
+    HEALTH_BAR_WIDTH * (player.heat / Player::MAX_HEAT).clamp(0.0, 1.0)
+}
+
+// Centered along the top, between the score and lives
+pub fn draw_boss_health(canvas: &mut graphics::Canvas, health_fraction: f32, screen_width: f32) {
+    let left = (screen_width - BOSS_BAR_WIDTH) / 2.0;
+
+    let background = graphics::Rect::new(left, BOSS_BAR_TOP, BOSS_BAR_WIDTH, BOSS_BAR_HEIGHT);
+    canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(background).color(HEALTH_BACKGROUND_COLOR));
+
+    let health = graphics::Rect::new(left, BOSS_BAR_TOP, BOSS_BAR_WIDTH * health_fraction.clamp(0.0, 1.0), BOSS_BAR_HEIGHT);
+    canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(health).color(BOSS_HEALTH_COLOR));
+}
+
+// What's been typed so far in typing mode, centered along the bottom of the screen
+pub fn draw_typing_buffer(canvas: &mut graphics::Canvas, ctx: &mut Context, typed: &str, screen_width: f32, screen_height: f32) {
+    let text = hud_text(&format!("> {}", typed));
+    let width = text.dimensions(ctx).map(|rect| rect.w).unwrap_or(0.0);
+    let pos = Point2 { x: (screen_width - width) / 2.0, y: screen_height - MARGIN - LINE_HEIGHT };
+    draw_shadowed(canvas, &text, pos);
+}
+
+// Bottom-left, out of the way of the rest of the HUD
+pub fn draw_fps(canvas: &mut graphics::Canvas, fps: f64, screen_height: f32) {
+    let text = hud_text(&fps_label(fps));
+    let pos = Point2 { x: MARGIN, y: screen_height - MARGIN - LINE_HEIGHT };
+    draw_shadowed(canvas, &text, pos);
+}
+
+pub fn fps_label(fps: f64) -> String {
+    format!("FPS: {}", fps.round())
+}
+
+// Same shadow as text sprites get, so the HUD stays readable over anything
+fn draw_shadowed(canvas: &mut graphics::Canvas, text: &graphics::Text, pos: Point2<f32>) {
+    let shadow_pos = Point2 { x: pos.x + TextSprite::SHADOW_OFFSET.x, y: pos.y + TextSprite::SHADOW_OFFSET.y };
+    canvas.draw(text, graphics::DrawParam::default().dest(shadow_pos).color(TextSprite::SHADOW_COLOR));
+    canvas.draw(text, graphics::DrawParam::default().dest(pos));
+}
+
+// The font is registered once when the assets are loaded, so this doesn't touch the filesystem
+fn hud_text(label: &str) -> graphics::Text {
+    let mut text = graphics::Text::new(label);
+    text.set_font(Assets::MAIN_FONT);
+    text.set_scale(graphics::PxScale::from(TEXT_SIZE));
+    text
+}
+
+// The points for a kill, drifting up from where the enemy died and fading out
+#[derive(Debug, Clone)]
+pub struct ScorePopup {
+    pub pos: Point2<f32>,
+    pub lifetime: f32,
+    // Built once, since the popup shows the same text for its whole life
+    text: graphics::Text,
+}
+
+impl ScorePopup {
+    pub const LIFETIME: f32 = 0.8;
+    // Pixels per second
+    pub const DRIFT_SPEED: f32 = 60.0;
+    pub const TEXT_SIZE: f32 = 20.0;
+    pub const COLOR: graphics::Color = graphics::Color::new(1.0, 0.9, 0.3, 1.0);
+
+    pub fn new(pos: Point2<f32>, points: u32) -> Self {
+        let mut text = graphics::Text::new(Self::label(points));
+        text.set_font(Assets::MAIN_FONT);
+        text.set_scale(graphics::PxScale::from(Self::TEXT_SIZE));
+        text.set_layout(graphics::TextLayout::center());
+
+        ScorePopup { pos, lifetime: Self::LIFETIME, text }
+    }
+
+    pub fn label(points: u32) -> String {
+        format!("+{}", points)
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.lifetime > 0.0
+    }
+
+    pub fn alpha(&self) -> f32 {
+        (self.lifetime / Self::LIFETIME).clamp(0.0, 1.0)
+    }
+
+    pub fn update(&mut self, seconds: f32) {
+        self.lifetime -= seconds;
+        self.pos.y -= Self::DRIFT_SPEED * seconds;
+    }
+
+    // Centered on `pos`, with the shadow fading along with the text
+    pub fn draw(&self, canvas: &mut graphics::Canvas) {
+        let alpha = self.alpha();
+        let shadow_pos = Point2 { x: self.pos.x + TextSprite::SHADOW_OFFSET.x, y: self.pos.y + TextSprite::SHADOW_OFFSET.y };
+        let shadow_color = graphics::Color { a: TextSprite::SHADOW_COLOR.a * alpha, ..TextSprite::SHADOW_COLOR };
+
+        canvas.draw(&self.text, graphics::DrawParam::default().dest(shadow_pos).color(shadow_color));
+        canvas.draw(&self.text, graphics::DrawParam::default().dest(self.pos).color(graphics::Color { a: alpha, ..Self::COLOR }));
+    }
+}
@@ -0,0 +1,184 @@
+use ggez::Context;
+use ggez::input::keyboard::KeyCode;
+use std::collections::HashMap;
+
+use crate::assets::read_text_file;
+
+// Stick deflection below this is treated as drift and ignored
+pub const GAMEPAD_DEADZONE: f32 = 0.15;
+
+// Normalizes an axis value to [-1, 1], zeroes it inside the deadzone, and rescales the rest so
+// movement starts from zero right at the edge of the deadzone
+pub fn apply_deadzone(value: f32) -> f32 {
+    let value = f32::clamp(value, -1.0, 1.0);
+
+    if value.abs() < GAMEPAD_DEADZONE {
+        0.0
+    } else {
+        value.signum() * (value.abs() - GAMEPAD_DEADZONE) / (1.0 - GAMEPAD_DEADZONE)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    Fire,
+    Pause,
+    Confirm,
+    Bomb,
+    QuickSave,
+    QuickLoad,
+    ToggleFps,
+    ToggleTyping,
+    ToggleHitboxes,
+    ToggleMute,
+    ToggleAutofire,
+}
+
+impl Action {
+    pub const ALL: [Action; 15] = [
+        Action::MoveLeft, Action::MoveRight, Action::MoveUp, Action::MoveDown,
+        Action::Fire, Action::Pause, Action::Confirm, Action::Bomb,
+        Action::QuickSave, Action::QuickLoad, Action::ToggleFps, Action::ToggleTyping,
+        Action::ToggleHitboxes, Action::ToggleMute, Action::ToggleAutofire,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::MoveLeft  => "move_left",
+            Action::MoveRight => "move_right",
+            Action::MoveUp    => "move_up",
+            Action::MoveDown  => "move_down",
+            Action::Fire      => "fire",
+            Action::Pause     => "pause",
+            Action::Confirm   => "confirm",
+            Action::Bomb      => "bomb",
+            Action::QuickSave => "quick_save",
+            Action::QuickLoad => "quick_load",
+            Action::ToggleFps => "toggle_fps",
+            Action::ToggleTyping => "toggle_typing",
+            Action::ToggleHitboxes => "toggle_hitboxes",
+            Action::ToggleMute => "toggle_mute",
+            Action::ToggleAutofire => "toggle_autofire",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputConfig {
+    bindings: HashMap<Action, KeyCode>,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        let bindings = HashMap::from([
+            (Action::MoveLeft,  KeyCode::Left),
+            (Action::MoveRight, KeyCode::Right),
+            (Action::MoveUp,    KeyCode::Up),
+            (Action::MoveDown,  KeyCode::Down),
+            (Action::Fire,      KeyCode::Space),
+            (Action::Pause,     KeyCode::Escape),
+            (Action::Confirm,   KeyCode::Return),
+            (Action::Bomb,      KeyCode::B),
+            (Action::QuickSave, KeyCode::F5),
+            (Action::QuickLoad, KeyCode::F9),
+            (Action::ToggleFps, KeyCode::F3),
+            (Action::ToggleTyping, KeyCode::Tab),
+            (Action::ToggleHitboxes, KeyCode::F2),
+            (Action::ToggleMute, KeyCode::M),
+            (Action::ToggleAutofire, KeyCode::F),
+        ]);
+
+        InputConfig { bindings }
+    }
+}
+
+impl InputConfig {
+    const PATH: &'static str = "/input.conf";
+
+    // Falls back to the default bindings if there's no config file or it can't be parsed
+    pub fn load(ctx: &Context) -> InputConfig {
+        let contents = match read_text_file(ctx, Self::PATH) {
+            Some(contents) => contents,
+            None => return InputConfig::default(),
+        };
+
+        InputConfig::parse(&contents).unwrap_or_else(|e| {
+            eprintln!("Warning: ignoring {}: {}", Self::PATH, e);
+            InputConfig::default()
+        })
+    }
+
+    // Parses lines like `fire = Z`, with `#` starting a comment. Actions that aren't mentioned keep
+    // their default key.
+    pub fn parse(contents: &str) -> Result<InputConfig, String> {
+        let mut config = InputConfig::default();
+
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (action, key) = line.split_once('=').
+                ok_or_else(|| format!("line {}: expected `action = key`", number + 1))?;
+            let action = Action::from_name(action.trim()).
+                ok_or_else(|| format!("line {}: unknown action `{}`", number + 1, action.trim()))?;
+            let key = parse_key(key.trim()).
+                ok_or_else(|| format!("line {}: unknown key `{}`", number + 1, key.trim()))?;
+
+            config.bind(action, key);
+        }
+
+        Ok(config)
+    }
+
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        self.bindings.insert(action, key);
+    }
+
+    pub fn key_for(&self, action: Action) -> KeyCode {
+        self.bindings[&action]
+    }
+
+    pub fn action_for(&self, key: KeyCode) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| self.bindings[action] == key)
+    }
+}
+
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let key = match name {
+        "A" => KeyCode::A, "B" => KeyCode::B, "C" => KeyCode::C, "D" => KeyCode::D,
+        "E" => KeyCode::E, "F" => KeyCode::F, "G" => KeyCode::G, "H" => KeyCode::H,
+        "I" => KeyCode::I, "J" => KeyCode::J, "K" => KeyCode::K, "L" => KeyCode::L,
+        "M" => KeyCode::M, "N" => KeyCode::N, "O" => KeyCode::O, "P" => KeyCode::P,
+        "Q" => KeyCode::Q, "R" => KeyCode::R, "S" => KeyCode::S, "T" => KeyCode::T,
+        "U" => KeyCode::U, "V" => KeyCode::V, "W" => KeyCode::W, "X" => KeyCode::X,
+        "Y" => KeyCode::Y, "Z" => KeyCode::Z,
+
+        "0" => KeyCode::Key0, "1" => KeyCode::Key1, "2" => KeyCode::Key2, "3" => KeyCode::Key3,
+        "4" => KeyCode::Key4, "5" => KeyCode::Key5, "6" => KeyCode::Key6, "7" => KeyCode::Key7,
+        "8" => KeyCode::Key8, "9" => KeyCode::Key9,
+
+        "Left" => KeyCode::Left, "Right" => KeyCode::Right, "Up" => KeyCode::Up, "Down" => KeyCode::Down,
+        "Space" => KeyCode::Space, "Return" | "Enter" => KeyCode::Return, "Escape" => KeyCode::Escape,
+        "F1" => KeyCode::F1, "F2" => KeyCode::F2, "F3" => KeyCode::F3, "F4" => KeyCode::F4,
+        "F5" => KeyCode::F5, "F6" => KeyCode::F6, "F7" => KeyCode::F7, "F8" => KeyCode::F8,
+        "F9" => KeyCode::F9, "F10" => KeyCode::F10, "F11" => KeyCode::F11, "F12" => KeyCode::F12,
+
+        "Tab" => KeyCode::Tab, "Back" => KeyCode::Back,
+        "LShift" => KeyCode::LShift, "RShift" => KeyCode::RShift,
+        "LControl" => KeyCode::LControl, "RControl" => KeyCode::RControl,
+        "LAlt" => KeyCode::LAlt, "RAlt" => KeyCode::RAlt,
+        _ => return None,
+    };
+
+    Some(key)
+}
@@ -0,0 +1,114 @@
+use ggez::mint::Point2;
+use ggez::{Context, GameError, GameResult};
+
+use crate::assets::read_text_file;
+use crate::entities::{Enemy, EnemyKind};
+use crate::spawner::{Spawn, SpawnEdge, Spawner};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelEvent {
+    pub time: f32,
+    pub spawn: Spawn,
+}
+
+// A handcrafted list of spawns. Each line of a level file is `time, x, enemy, speed`, where
+// `enemy` is one of `Spawner::ENEMIES` with any line break written as `\n`. Blank lines and
+// anything after a `#` are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    events: Vec<LevelEvent>,
+    next_event: usize,
+}
+
+impl Level {
+    pub fn load(ctx: &Context, path: &str) -> GameResult<Level> {
+        let contents = read_text_file(ctx, path).
+            ok_or_else(|| GameError::ResourceLoadError(format!("{}: couldn't read the level", path)))?;
+
+        Level::parse(&contents).
+            map_err(|e| GameError::ResourceLoadError(format!("{}: {}", path, e)))
+    }
+
+    pub fn parse(contents: &str) -> Result<Level, String> {
+        let mut events = Vec::new();
+
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let event = parse_event(line).map_err(|e| format!("line {}: {}", number + 1, e))?;
+            events.push(event);
+        }
+
+        // Stable, so events at the same time keep the order they were written in
+        events.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+        Ok(Level { events, next_event: 0 })
+    }
+
+    pub fn events(&self) -> &[LevelEvent] {
+        &self.events
+    }
+
+    pub fn restart(&mut self) {
+        self.next_event = 0;
+    }
+
+    // Drops everything scheduled before `elapsed`, for picking a level back up partway through
+    pub fn skip_to(&mut self, elapsed: f32) {
+        self.next_event = self.events.iter().take_while(|event| event.time < elapsed).count();
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_event >= self.events.len()
+    }
+
+    pub fn tick(&mut self, elapsed: f32, ctx: &mut Context) -> GameResult<Vec<Enemy>> {
+        self.due_spawns(elapsed).
+            into_iter().
+            map(|spawn| spawn.into_enemy(ctx)).
+            collect()
+    }
+
+    // Every spawn scheduled at or before `elapsed` that hasn't been handed out yet
+    pub fn due_spawns(&mut self, elapsed: f32) -> Vec<Spawn> {
+        let due = self.events[self.next_event..].
+            iter().
+            take_while(|event| event.time <= elapsed).
+            map(|event| event.spawn.clone()).
+            collect::<Vec<_>>();
+
+        self.next_event += due.len();
+        due
+    }
+}
+
+fn parse_event(line: &str) -> Result<LevelEvent, String> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields.len() != 4 {
+        return Err(format!("expected `time, x, enemy, speed` but found {} fields", fields.len()));
+    }
+
+    let number = |field: &str, name: &str| {
+        field.parse::<f32>().
+            ok().
+            filter(|value| value.is_finite() && *value >= 0.0).
+            ok_or_else(|| format!("{} `{}` isn't a non-negative number", name, field))
+    };
+
+    let time = number(fields[0], "time")?;
+    let x = number(fields[1], "x")?;
+    let speed = number(fields[3], "speed")?;
+
+    let name = fields[2].replace("\\n", "\n");
+    let label = Spawner::ENEMIES.iter().
+        find(|label| **label == name).
+        ok_or_else(|| format!("unknown enemy `{}`", fields[2]))?;
+
+    Ok(LevelEvent {
+        time,
+        spawn: Spawn { kind: EnemyKind::Grunt, label: String::from(*label), pos: Point2 { x, y: 0.0 }, speed, fire_interval: None, seeks_player: false, edge: SpawnEdge::Top },
+    })
+}
@@ -1,3 +1,29 @@
 pub mod entities;
 pub mod assets;
+pub mod boss;
+pub mod camera;
+pub mod collision;
 pub mod debug;
+pub mod difficulty;
+pub mod formation;
+pub mod highscores;
+pub mod hud;
+pub mod input;
+pub mod level;
+pub mod movement;
+pub mod particles;
+pub mod pool;
+pub mod replay;
+pub mod rng;
+pub mod score;
+pub mod screen;
+pub mod snapshot;
+pub mod spawner;
+pub mod starfield;
+pub mod state;
+pub mod time;
+pub mod timeline;
+pub mod tuning;
+pub mod typing;
+pub mod viewport;
+pub mod words;
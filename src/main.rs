@@ -1,236 +1,963 @@
-use ggez::audio::SoundSource;
 use ggez::conf::{Conf, WindowMode};
 use ggez::event;
 use ggez::graphics::{self, Drawable};
+use ggez::input::gamepad::{gilrs, GamepadId};
 use ggez::input::keyboard;
-use ggez::mint::Point2;
+use ggez::mint::{Point2, Vector2};
 use ggez::{Context, ContextBuilder, GameResult};
-use rand::Rng;
-use rand::rngs::ThreadRng;
-
-use shooter::entities::{Player, PlayerState, Shot, Enemy};
-use shooter::assets::{Assets, TextSprite};
+use rand::{Rng, SeedableRng};
+use rand::seq::SliceRandom;
+use rand::rngs::StdRng;
+
+use shooter::entities::{self, Player, PlayerEvent, Shot, Enemy, EnemyEvent, Laser, Powerup, PowerupKind};
+use shooter::assets::{Assets, Skin};
+use shooter::boss::Boss;
+use shooter::camera::{Camera, DamageFlash, ScreenShake};
+use shooter::score::{Combo, Score, WaveTracker};
+use shooter::screen::ScreenConfig;
+use shooter::snapshot::{self, EnemySnapshot, GameSnapshot, PlayerSnapshot, ShotSnapshot};
+use shooter::difficulty::Difficulty;
+use shooter::spawner::Spawner;
+use shooter::words::WordList;
+use shooter::starfield::Starfield;
+use shooter::particles::{MuzzleFlash, ParticleSystem};
+use shooter::pool::ShotPool;
+use shooter::replay::{FrameInput, Recorder, Replay, Replayer};
+use shooter::highscores::HighScores;
+use shooter::state::GameState;
+use shooter::collision::{Collidable, QuadTree};
 use shooter::debug;
+use shooter::rng;
+use shooter::time::{self, GameClock, HitStop};
+use shooter::tuning::Tuning;
+use shooter::hud::{self, ScorePopup};
+use shooter::viewport::Viewport;
+use shooter::typing::{Keystroke, TypingState};
+use shooter::level::Level;
+use shooter::input::{self, Action, InputConfig};
 
 use std::env;
 use std::path;
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 struct InputState {
     move_left: bool,
     move_right: bool,
+    move_up: bool,
+    move_down: bool,
     fire: bool,
+    // Set when bomb is pressed and used up on the next tick
+    bomb: bool,
+    stick: Vector2<f32>,
+    gamepad_fire: bool,
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        InputState {
+            move_left: false,
+            move_right: false,
+            move_up: false,
+            move_down: false,
+            fire: false,
+            bomb: false,
+            stick: Vector2 { x: 0.0, y: 0.0 },
+            gamepad_fire: false,
+        }
+    }
 }
 
 impl InputState {
-    fn move_amount(&self) -> f32 {
-        let mut amount = 0.0;
-        if self.move_left { amount += -1.0; }
-        if self.move_right { amount += 1.0; }
+    fn move_amount(&self) -> Vector2<f32> {
+        let mut amount = Vector2 { x: 0.0, y: 0.0 };
+        if self.move_left { amount.x += -1.0; }
+        if self.move_right { amount.x += 1.0; }
+        if self.move_up { amount.y += -1.0; }
+        if self.move_down { amount.y += 1.0; }
+
+        amount.x = f32::clamp(amount.x + self.stick.x, -1.0, 1.0);
+        amount.y = f32::clamp(amount.y + self.stick.y, -1.0, 1.0);
         amount
     }
 }
 
 struct MainState {
-    rng: ThreadRng,
-    game_over: bool,
+    rng: StdRng,
+    state: GameState,
     killed_by: String,
-    score: u32,
+    score: Score,
+    combo: Combo,
+    waves: WaveTracker,
+    // Counts down while the perfect clear message is showing
+    perfect_clear_timer: f32,
+    high_scores: HighScores,
     assets: Assets,
     input: InputState,
+    input_config: InputConfig,
+    tuning: Tuning,
     player: Player,
-    shots: Vec<Shot>,
+    shots: ShotPool,
+    enemy_shots: Vec<Shot>,
+    powerups: Vec<Powerup>,
     enemies: Vec<Enemy>,
-    time_until_next_enemy: f32,
-    screen_width: f32,
-    screen_height: f32,
+    boss: Option<Boss>,
+    time_until_boss: f32,
+    collision_tree: QuadTree,
+    particles: ParticleSystem,
+    muzzle_flashes: Vec<MuzzleFlash>,
+    score_popups: Vec<ScorePopup>,
+    spawner: Spawner,
+    words: WordList,
+    // Picked in the menu and kept between games
+    skin: Skin,
+    autofire: bool,
+    level: Option<Level>,
+    difficulty: Difficulty,
+    screen_shake: ScreenShake,
+    starfield: Starfield,
+    shake_offset: Vector2<f32>,
+    camera: Camera,
+    damage_flash: DamageFlash,
+    hit_stop: HitStop,
+    clock: GameClock,
+    time_scale: f32,
+    show_fps: bool,
+    debug_draw: bool,
+    typing_mode: bool,
+    typing: TypingState,
+    screen: ScreenConfig,
+    viewport: Viewport,
+    // Set RECORD to record every game to the replay file
+    recording: bool,
+    recorder: Option<Recorder>,
+    replayer: Option<Replayer>,
 }
 
 impl MainState {
-    const ENEMIES: [&'static str; 7] = [
-        "Segfaults", "Undefined Behaviour",
-        "NULLs", "Inefficiencies", "Bloat",
-        "Unnecessary Heap\nAllocations", "Data Races"
-    ];
-
-    fn new(ctx: &mut Context, conf: &Conf) -> GameResult<MainState> {
+    const PLAYER_NAME: &'static str = "Ferris";
+    const KILL_SHAKE: f32 = 8.0;
+    // Kills worth at least this many points freeze the game for a moment
+    const HIT_STOP_MIN_POINTS: u32 = 5;
+    const HIT_STOP_TIME: f32 = 0.05;
+    const BOSS_HIT_STOP_TIME: f32 = 0.15;
+    const POWERUP_DROP_CHANCE: f64 = 0.1;
+    const STAR_COUNT: usize = 150;
+    const SHOT_POOL_SIZE: usize = 64;
+    const PARTICLE_CAPACITY: usize = 1000;
+    // Bombing is a panic button, so it's not worth as much as shooting enemies down
+    const BOMB_POINTS_FRACTION: f32 = 0.5;
+    const BOSS_INTERVAL: f32 = 90.0;
+    const GRAZE_POINTS: u32 = 1;
+    const PERFECT_CLEAR_SHOW_TIME: f32 = 2.0;
+
+    fn new(ctx: &mut Context, conf: &Conf, seed: u64) -> GameResult<MainState> {
         let assets = Assets::new(ctx)?;
-        let screen_width = conf.window_mode.width;
-        let screen_height = conf.window_mode.height;
+        let screen = ScreenConfig::new(conf.window_mode.width, conf.window_mode.height);
 
-        // Player starts in bottom-middle of the screen
-        let player_pos = Point2 {
-            x: screen_width / 2.0,
-            y: screen_height,
-        };
+        let mut rng = StdRng::seed_from_u64(seed);
+        let words = WordList::load(ctx);
+        let spawner = Spawner::new(screen.width, rng::fork(&mut rng)).with_words(words.clone());
+        let screen_shake = ScreenShake::new(rng::fork(&mut rng));
+        let starfield = Starfield::new(Self::STAR_COUNT, screen.width, screen.height, &mut rng::fork(&mut rng));
+
+        let high_scores = HighScores::load(ctx);
+        let score = Score { current: 0, high: high_scores.best() };
 
         let s = MainState {
-            rng: rand::thread_rng(),
-            game_over: false,
-            score: 0,
+            rng,
+            state: GameState::Menu,
+            score,
+            combo: Combo::new(),
+            waves: WaveTracker::new(),
+            perfect_clear_timer: 0.0,
+            high_scores,
             killed_by: String::new(),
-            assets: assets,
+            assets,
             input: InputState::default(),
-            player: Player::new(player_pos),
-            shots: Vec::new(),
+            input_config: InputConfig::load(ctx),
+            tuning: Tuning::load(ctx),
+            player: Player::new(Self::player_start(&screen)),
+            shots: ShotPool::new(Self::SHOT_POOL_SIZE),
+            enemy_shots: Vec::new(),
+            powerups: Vec::new(),
             enemies: Vec::new(),
-            time_until_next_enemy: 1.0,
-            screen_width: conf.window_mode.width,
-            screen_height: conf.window_mode.height,
+            boss: None,
+            time_until_boss: Self::BOSS_INTERVAL,
+            collision_tree: QuadTree::new(graphics::Rect::new(0.0, 0.0, screen.width, screen.height)),
+            particles: ParticleSystem::new(Self::PARTICLE_CAPACITY),
+            muzzle_flashes: Vec::new(),
+            score_popups: Vec::new(),
+            spawner,
+            words,
+            skin: Skin::DEFAULT,
+            autofire: false,
+            level: Self::load_level(ctx),
+            difficulty: Difficulty::new(),
+            screen_shake,
+            starfield,
+            shake_offset: Vector2 { x: 0.0, y: 0.0 },
+            // The world is the same size as the screen for now, so the camera stays put
+            camera: Camera::new(screen, screen),
+            damage_flash: DamageFlash::new(),
+            hit_stop: HitStop::new(),
+            clock: GameClock::new(),
+            time_scale: 1.0,
+            show_fps: false,
+            debug_draw: debug::is_active(),
+            typing_mode: false,
+            typing: TypingState::new(),
+            screen,
+            viewport: Viewport::new(screen, screen),
+            recording: env::var("RECORD").is_ok(),
+            recorder: None,
+            replayer: None,
         };
 
         Ok(s)
     }
 
+    // Set LEVEL to a level file, like /level1.txt, to play that instead of random spawns
+    fn load_level(ctx: &Context) -> Option<Level> {
+        let path = env::var("LEVEL").ok()?;
+        Level::load(ctx, &path).
+            map_err(|e| eprintln!("Warning: couldn't load level, spawning randomly instead: {}", e)).
+            ok()
+    }
+
+    // Player starts in bottom-middle of the screen
+    fn player_start(screen: &ScreenConfig) -> Point2<f32> {
+        Point2 {
+            x: screen.width / 2.0,
+            y: screen.height,
+        }
+    }
+
+    // Each game gets its own seed, so it can be recorded and replayed on its own
+    fn start_game(&mut self) {
+        let seed = match &self.replayer {
+            Some(replayer) => replayer.seed(),
+            None => self.rng.gen(),
+        };
+        self.rng = StdRng::seed_from_u64(seed);
+        if self.recording {
+            self.recorder = Some(Recorder::new(seed));
+        }
+
+        self.score.reset();
+        self.combo = Combo::new();
+        self.waves = WaveTracker::new();
+        self.perfect_clear_timer = 0.0;
+        self.killed_by.clear();
+        self.player = Player::new(Self::player_start(&self.screen)).
+            with_skin(self.skin).
+            with_autofire(self.autofire).
+            with_tuning(self.tuning);
+        self.shots.clear();
+        self.enemy_shots.clear();
+        self.powerups.clear();
+        self.enemies.clear();
+        self.boss = None;
+        self.time_until_boss = Self::BOSS_INTERVAL;
+        self.particles.clear();
+        self.muzzle_flashes.clear();
+        self.score_popups.clear();
+        self.spawner = Spawner::new(self.screen.width, rng::fork(&mut self.rng)).
+            with_words(self.words.clone()).
+            with_side_spawn_chance(Spawner::SIDE_SPAWN_CHANCE);
+        self.difficulty = Difficulty::new();
+        if let Some(level) = &mut self.level {
+            level.restart();
+        }
+        self.screen_shake = ScreenShake::new(rng::fork(&mut self.rng));
+        self.shake_offset = Vector2 { x: 0.0, y: 0.0 };
+        self.camera.center_on(self.player.pos);
+        self.hit_stop = HitStop::new();
+        self.clock.reset();
+        self.damage_flash = DamageFlash::new();
+        self.time_scale = 1.0;
+        self.typing.clear();
+    }
+
+    // Music only plays during a game, and holds its place while paused
+    fn update_music(&mut self, ctx: &mut Context) {
+        match self.state {
+            GameState::Playing => self.assets.play_music(ctx),
+            GameState::Paused => self.assets.pause_music(),
+            GameState::Menu | GameState::GameOver => self.assets.stop_music(ctx),
+        }
+    }
+
+    fn start_replay(&mut self, replay: Replay, ctx: &mut Context) {
+        self.replayer = Some(Replayer::new(replay));
+        self.start_game();
+        self.state = GameState::Playing;
+        self.update_music(ctx);
+    }
+
+    // The recording ran out before the game ended
+    fn stop_replay(&mut self, ctx: &mut Context) {
+        self.replayer = None;
+        self.state = GameState::Menu;
+        self.update_music(ctx);
+    }
+
+    fn end_game(&mut self, killed_by: &str, ctx: &mut Context) {
+        self.state = GameState::GameOver;
+        self.update_music(ctx);
+        self.killed_by = String::from(killed_by);
+
+        self.replayer = None;
+        if let Some(recorder) = self.recorder.take() {
+            if let Err(e) = recorder.finish().save(ctx) {
+                eprintln!("Warning: couldn't save the replay: {}", e);
+            }
+        }
+
+        if self.high_scores.insert(Self::PLAYER_NAME, self.score.current) {
+            let _ = self.high_scores.save(ctx);
+        }
+    }
+
+    fn full_screen(&self) -> graphics::Rect {
+        graphics::Rect::new(0.0, 0.0, self.screen.width, self.screen.height)
+    }
+
+    fn draw_centered_text(&self, label: &str, canvas: &mut graphics::Canvas, ctx: &mut Context) {
+        let mut text = graphics::Text::new(label);
+        text.set_font(Assets::MAIN_FONT);
+        text.set_scale(graphics::PxScale::from(40.0));
+
+        let top_left = Point2 {
+            x: (self.screen.width - text.dimensions(ctx).unwrap().w) / 2.0,
+            y: (self.screen.height - text.dimensions(ctx).unwrap().h) / 2.0,
+        };
+        canvas.draw(&text, graphics::DrawParam::default().dest(top_left));
+    }
+
+    fn draw_game(&mut self, canvas: &mut graphics::Canvas, ctx: &mut Context) -> GameResult<()> {
+        let view = self.camera.view_rect(self.shake_offset);
+        canvas.set_screen_coordinates(self.viewport.letterbox(view));
+
+        self.starfield.draw(canvas);
+        for warning in self.spawner.warnings() {
+            warning.draw(canvas);
+        }
+
+        self.player.draw(canvas, &self.assets);
+
+        for shot in self.shots.iter_mut().chain(self.enemy_shots.iter_mut()) {
+            shot.draw(canvas, &self.assets);
+        }
+
+        // Beams go under the enemies firing them
+        for enemy in self.enemies.iter() {
+            if let Some(laser) = enemy.laser() {
+                laser.draw(enemy.bounding_rect().bottom(), self.screen.height, canvas);
+            }
+        }
+
+        for enemy in self.enemies.iter_mut() {
+            enemy.draw_shadow(canvas);
+        }
+        if let Some(boss) = &mut self.boss {
+            boss.enemy.draw_shadow(canvas);
+        }
+
+        for enemy in self.enemies.iter_mut() {
+            if self.typing_mode && self.typing.target() == Some(enemy.id()) {
+                enemy.draw_highlighted(canvas);
+            } else {
+                enemy.draw(canvas);
+            }
+            enemy.draw_type_marker(canvas, ctx)?;
+            enemy.draw_health_bar(canvas);
+        }
+
+        if let Some(boss) = &mut self.boss {
+            boss.enemy.draw(canvas);
+            boss.enemy.draw_type_marker(canvas, ctx)?;
+        }
+
+        for powerup in self.powerups.iter() {
+            powerup.draw(canvas);
+        }
+
+        self.particles.draw(canvas);
+        for flash in self.muzzle_flashes.iter() {
+            flash.draw(canvas);
+        }
+        for popup in self.score_popups.iter() {
+            popup.draw(canvas);
+        }
+
+        // Drawn last so the outlines end up on top of the sprites
+        if self.debug_draw {
+            let hitboxes = self.enemies.iter().map(Collidable::bounding_rect).
+                chain(self.shots.iter().map(Collidable::bounding_rect)).
+                chain(self.enemy_shots.iter().map(Collidable::bounding_rect)).
+                chain(self.powerups.iter().map(Collidable::bounding_rect)).
+                chain(self.boss.iter().map(|boss| boss.enemy.bounding_rect())).
+                chain(std::iter::once(self.player.bounding_rect()));
+            for hitbox in hitboxes {
+                debug::draw_outline(hitbox, canvas, ctx)?;
+            }
+        }
+
+        // The HUD shouldn't shake with the rest of the screen
+        canvas.set_screen_coordinates(self.viewport.letterbox(self.full_screen()));
+        hud::draw_hud(canvas, ctx, &self.score, &self.combo, &self.player, self.screen.width)?;
+        if let Some(boss) = &self.boss {
+            hud::draw_boss_health(canvas, boss.health_fraction(), self.screen.width);
+        }
+        if self.perfect_clear_timer > 0.0 {
+            let label = format!("Perfect clear! +{}", WaveTracker::PERFECT_CLEAR_BONUS);
+            self.draw_centered_text(&label, canvas, ctx);
+        }
+        if self.typing_mode {
+            hud::draw_typing_buffer(canvas, ctx, self.typing.buffer(), self.screen.width, self.screen.height);
+        }
+
+        self.damage_flash.draw(self.full_screen(), canvas);
+
+        // The frame underneath stays frozen since nothing updates while paused
+        if self.state == GameState::Paused {
+            self.draw_pause_overlay(canvas, ctx)?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_pause_overlay(&self, canvas: &mut graphics::Canvas, ctx: &mut Context) -> GameResult<()> {
+        let screen = self.full_screen();
+        let dim = graphics::Color::new(0.0, 0.0, 0.0, 0.6);
+
+        canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(screen).color(dim));
+        self.draw_centered_text("PAUSED", canvas, ctx);
+        Ok(())
+    }
+
+    fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            player: PlayerSnapshot::capture(&self.player),
+            enemies: self.enemies.iter().filter(|enemy| enemy.is_alive).map(EnemySnapshot::capture).collect(),
+            shots: self.shots.iter().map(ShotSnapshot::capture).collect(),
+            enemy_shots: self.enemy_shots.iter().filter(|shot| shot.is_alive).map(ShotSnapshot::capture).collect(),
+            score: self.score.current,
+            elapsed: self.difficulty.elapsed,
+        }
+    }
+
+    fn restore(&mut self, snapshot: GameSnapshot, ctx: &mut Context) -> GameResult<()> {
+        self.start_game();
+
+        snapshot.player.restore(&mut self.player);
+        for enemy in snapshot.enemies {
+            self.enemies.push(enemy.into_enemy(ctx)?);
+        }
+        for shot in snapshot.shots {
+            self.shots.add(shot.into_shot());
+        }
+        self.enemy_shots.extend(snapshot.enemy_shots.into_iter().map(ShotSnapshot::into_shot));
+        self.score.add(snapshot.score);
+        self.difficulty.elapsed = snapshot.elapsed;
+        if let Some(level) = &mut self.level {
+            level.skip_to(snapshot.elapsed);
+        }
+        self.state = GameState::Playing;
+
+        Ok(())
+    }
+
+    fn detonate_bomb(&mut self, ctx: &mut Context) {
+        if !self.player.use_bomb() {
+            return;
+        }
+
+        for enemy in self.enemies.iter().filter(|enemy| enemy.is_alive) {
+            let (center, _) = enemy.bounding_circle();
+            self.particles.explode(center, &mut self.rng);
+        }
+
+        let points = entities::detonate_bomb(&mut self.enemies, &mut self.enemy_shots, Self::BOMB_POINTS_FRACTION);
+        self.score.add(points);
+        self.screen_shake.trigger(ScreenShake::MAX_INTENSITY);
+        self.assets.play_explosion(ctx);
+    }
+
     fn handle_collisions(&mut self, ctx: &mut Context) {
-        for enemy in &mut self.enemies {
-            for shot in &mut self.shots {
-                if enemy.bounding_rect(ctx).contains(shot.pos) {
+        entities::cancel_shots(self.shots.iter_mut(), &mut self.enemy_shots);
+
+        self.collision_tree.clear();
+        for (id, enemy) in self.enemies.iter().enumerate() {
+            self.collision_tree.insert(id, enemy.bounding_rect());
+        }
+
+        let mut destroyed = Vec::new();
+        for shot in self.shots.iter_mut() {
+            // Look along the whole path since last frame, so fast shots can't skip over enemies
+            let query = match shot.previous_pos() {
+                Some(prev_pos) => shot.swept_rect(prev_pos),
+                None => shot.bounding_rect(),
+            };
+
+            for id in self.collision_tree.query(query) {
+                let enemy = &mut self.enemies[id];
+
+                // Piercing shots keep going, so don't stop at the first enemy
+                match shot.strike(enemy) {
+                    Some(true) => destroyed.push(id),
+                    Some(false) => enemy.apply_knockback(Vector2 { x: 0.0, y: -Enemy::KNOCKBACK_IMPULSE }),
+                    None => (),
+                }
+            }
+        }
+
+        for id in destroyed {
+            self.enemy_destroyed(id, ctx);
+        }
+
+        // Piercing doesn't work on the boss, every shot that hits it stops there
+        let mut boss_destroyed = false;
+        if let Some(boss) = &mut self.boss {
+            for shot in self.shots.iter_mut() {
+                if boss.is_alive() && shot.hits(&boss.enemy) {
                     shot.is_alive = false;
-                    enemy.is_alive = false;
-                    self.score += 1;
-                    let _ = self.assets.boom_sound.play(ctx);
+                    boss_destroyed |= boss.take_damage(shot.damage);
                 }
             }
         }
+
+        if boss_destroyed {
+            let boss = self.boss.take().unwrap();
+            let (center, _) = boss.enemy.bounding_circle();
+            self.particles.explode(center, &mut self.rng);
+            let points = boss.enemy.point_value * self.combo.register_kill();
+            self.score.add(points);
+            self.score_popups.push(ScorePopup::new(center, points));
+            self.screen_shake.trigger(ScreenShake::MAX_INTENSITY);
+            self.hit_stop.trigger(Self::BOSS_HIT_STOP_TIME);
+            self.assets.play_explosion(ctx);
+        }
+    }
+
+    // Rewards for shooting down the enemy at `index`, which has already been marked as dead
+    fn enemy_destroyed(&mut self, index: usize, ctx: &mut Context) {
+        let enemy = &self.enemies[index];
+        let (center, _) = enemy.bounding_circle();
+        self.particles.explode(center, &mut self.rng);
+        let points = enemy.point_value * self.combo.register_kill();
+        self.score.add(points);
+        self.score_popups.push(ScorePopup::new(center, points));
+        self.screen_shake.trigger(Self::KILL_SHAKE);
+        if enemy.point_value >= Self::HIT_STOP_MIN_POINTS {
+            self.hit_stop.trigger(Self::HIT_STOP_TIME);
+        }
+        self.difficulty.performance.record_kill();
+        if self.rng.gen_bool(Self::POWERUP_DROP_CHANCE) {
+            let kinds = [
+                PowerupKind::RapidFire, PowerupKind::Spread, PowerupKind::ExtraLife,
+                PowerupKind::Piercing, PowerupKind::Bomb, PowerupKind::Freeze,
+            ];
+            let kind = *kinds.choose(&mut self.rng).unwrap();
+            self.powerups.push(Powerup::new(enemy.pos, kind));
+        }
+        let children = enemy.on_death();
+        for child in children.iter() {
+            self.waves.record_spawn(child.id());
+        }
+        self.enemies.extend(children);
+        self.assets.play_explosion(ctx);
     }
 }
 
 
 impl event::EventHandler for MainState {
     fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
-        if self.game_over {
-            return Ok(());
-        }
-
         const DESIRED_TICKS_PER_SEC: u32 = 60;
 
+        // Every tick is the same fixed step, so a stall shows up as time the loop is behind by. After
+        // one, only catch up by MAX_DELTA worth of ticks and drop the rest.
+        let tick_seconds = 1.0 / DESIRED_TICKS_PER_SEC as f32;
+        let behind = ctx.time.remaining_update_time().as_secs_f32();
+        let max_ticks = time::catch_up_ticks(behind, tick_seconds);
+        let mut ticks = 0;
+
         while ctx.time.check_update_time(DESIRED_TICKS_PER_SEC) {
-            let seconds = 1.0 / DESIRED_TICKS_PER_SEC as f32;
+            // Ticks still get consumed outside of play, so resuming doesn't fast-forward
+            let paused = !self.state.is_simulating() || ticks >= max_ticks;
+            // Frozen ticks aren't recorded, a replay freezes on the same kills by itself
+            let frozen = !paused && self.hit_stop.update(tick_seconds);
+            self.clock.set_paused(paused || frozen);
+            self.clock.tick(tick_seconds, self.time_scale);
+
+            if paused {
+                continue;
+            }
+            ticks += 1;
+            if frozen {
+                continue;
+            }
+
+            let seconds = self.clock.delta();
+            let raw_seconds = self.clock.real_delta();
 
             // Input
             // Need both checks if key was pressed for less than one frame
-            self.input.fire = ctx.keyboard.is_key_pressed(keyboard::KeyCode::Space) ||
-                ctx.keyboard.is_key_just_pressed(keyboard::KeyCode::Space);
+            // Typing mode shoots by typing instead, so space is just another character
+            let fire_key = self.input_config.key_for(Action::Fire);
+            self.input.fire = !self.typing_mode && (
+                ctx.keyboard.is_key_pressed(fire_key) ||
+                ctx.keyboard.is_key_just_pressed(fire_key) ||
+                self.input.gamepad_fire);
+
+            // Replays take over from the live input, and everything that's played gets recorded
+            let live_input = FrameInput::new(self.input.move_amount(), self.input.fire, self.input.bomb);
+            self.input.bomb = false;
+            let frame = match &mut self.replayer {
+                Some(replayer) => match replayer.next_frame() {
+                    Some(frame) => frame,
+                    None => {
+                        self.stop_replay(ctx);
+                        continue;
+                    },
+                },
+                None => live_input,
+            };
+            if let Some(recorder) = &mut self.recorder {
+                recorder.record(frame);
+            }
 
-            // Spawn enemies
-            self.time_until_next_enemy -= seconds;
-            if self.time_until_next_enemy <= 0.0 {
-                let random_point = Point2 {
-                    x: self.rng.gen_range(0.0 .. self.screen_width - 100.0),
-                    y: 0.0,
-                };
-                let random_text = Self::ENEMIES[self.rng.gen_range(0 .. Self::ENEMIES.len())];
-                let random_speed = self.rng.gen_range(50.0 .. 200.0);
+            if frame.bomb {
+                self.detonate_bomb(ctx);
+            }
 
-                let enemy_sprite = Box::new(TextSprite::new(random_text)?);
-                let enemy = Enemy::new(random_text, random_point, random_speed, enemy_sprite)?;
+            // Spawn enemies
+            self.difficulty.update(seconds);
+            let new_enemies = match &mut self.level {
+                Some(level) => level.tick(self.difficulty.elapsed, ctx)?,
+                None => self.spawner.tick(seconds, &self.difficulty, ctx)?,
+            };
+            for enemy in new_enemies.iter() {
+                self.waves.record_spawn(enemy.id());
+            }
+            self.enemies.extend(new_enemies);
 
-                self.enemies.push(enemy);
-                self.time_until_next_enemy = self.rng.gen_range(0.5 .. 1.8);
+            self.perfect_clear_timer = f32::max(self.perfect_clear_timer - raw_seconds, 0.0);
+            if let Some(bonus) = self.waves.update(seconds) {
+                self.score.add(bonus);
+                self.perfect_clear_timer = Self::PERFECT_CLEAR_SHOW_TIME;
             }
 
             // Update player state
-            self.player.update(self.input.move_amount(), seconds, self.screen_width);
-            self.player.time_until_next_shot -= seconds;
-            if self.input.fire && self.player.time_until_next_shot < 0.0 {
-                let shot_pos = Point2 {
-                    x: self.player.pos.x - 75.0,
-                    y: self.player.pos.y - 80.0,
-                };
-                let shot = Shot::new(shot_pos);
-                self.shots.push(shot);
+            let play_area = Player::play_area(&self.screen);
+            self.player.update(frame.move_amount(), seconds, play_area);
+
+            let mut fired = false;
+            for event in self.player.update_weapon(frame.fire, seconds) {
+                if let PlayerEvent::FiredShot(shot) = event {
+                    self.shots.add(shot);
+                    fired = true;
+                }
+            }
+            if fired {
+                self.muzzle_flashes.push(MuzzleFlash::new(self.player.muzzle_position()));
+                self.assets.play_shoot(ctx);
+            }
 
-                let _ = self.assets.shot_sound.play(ctx);
+            for shot in self.shots.iter_mut() {
+                shot.home_toward(&self.enemies, seconds);
+            }
+            self.shots.update_all(seconds);
 
-                self.player.time_until_next_shot = Player::SHOT_TIMEOUT;
-                self.player.state = PlayerState::Shooting;
-            } else if !self.input.fire {
-                self.player.state = PlayerState::Normal;
+            self.particles.update(seconds);
+            for flash in self.muzzle_flashes.iter_mut() {
+                flash.update(seconds);
+            }
+            for popup in self.score_popups.iter_mut() {
+                popup.update(seconds);
             }
 
-            for shot in self.shots.iter_mut() {
-                shot.update(seconds);
+            self.combo.decay(seconds);
+            self.shake_offset = self.screen_shake.update(seconds);
+            self.camera.follow(self.player.pos, seconds);
+            self.damage_flash.update(seconds);
+            self.starfield.update(seconds);
+
+            for shot in self.enemy_shots.iter_mut() {
+                shot.update_with_bounds(seconds, self.screen.height);
             }
 
+            let mut killed_by = None;
             for enemy in self.enemies.iter_mut() {
-                enemy.update(seconds);
-
-                if enemy.pos.y >= self.screen_height {
-                    if debug::is_active() {
-                        // We don't end the game in debug mode, but we do make sure the enemy is
-                        // cleaned up from the enemy array
-                        enemy.is_alive = false;
-                    } else {
-                        self.game_over = true;
-                        self.killed_by = String::from(enemy.label());
-                        let _ = self.assets.boom_sound.play(ctx);
+                enemy.track(self.player.pos);
+                for event in enemy.step(seconds, self.screen.width, self.screen.height) {
+                    match event {
+                        EnemyEvent::FiredShot(shot) => self.enemy_shots.push(shot),
+
+                        // We don't end the game in debug mode, the escaped enemy just gets cleaned up
+                        EnemyEvent::ReachedBottom if !debug::is_active() => {
+                            self.waves.record_escape(enemy.id());
+                            killed_by = Some(String::from(enemy.label()));
+                            self.assets.play_explosion(ctx);
+                        },
+
+                        EnemyEvent::ReachedBottom => self.waves.record_escape(enemy.id()),
                     }
                 }
             }
 
+            // A boss turns up every so often, and the next one's countdown starts once it's beaten
+            if self.boss.is_none() {
+                self.time_until_boss -= seconds;
+                if self.time_until_boss <= 0.0 {
+                    self.boss = Some(Boss::spawn(self.screen.width, ctx)?);
+                    self.time_until_boss = Self::BOSS_INTERVAL;
+                }
+            }
+            if let Some(boss) = &mut self.boss {
+                self.enemy_shots.extend(boss.update(seconds, self.screen.width));
+            }
+
             self.handle_collisions(ctx);
 
-            self.shots.retain(|shot| shot.is_alive && shot.pos.y >= 0.0);
+            for powerup in self.powerups.iter_mut() {
+                powerup.update(seconds, self.screen.height);
+                if powerup.collect(&mut self.player) && powerup.kind == PowerupKind::Freeze {
+                    let bosses = self.boss.iter_mut().map(|boss| &mut boss.enemy);
+                    for enemy in self.enemies.iter_mut().chain(bosses) {
+                        enemy.slow(Powerup::FREEZE_FACTOR, Powerup::FREEZE_DURATION);
+                    }
+                }
+            }
+
+            let lives_before = self.player.lives;
+            // Losing a life resets health, so compare both
+            let health_before = (self.player.lives, self.player.health);
+            for enemy in self.enemies.iter_mut() {
+                if enemy.ram(&mut self.player) {
+                    let (center, _) = enemy.bounding_circle();
+                    self.particles.explode(center, &mut self.rng);
+                    self.screen_shake.trigger(Self::KILL_SHAKE);
+                    self.assets.play_explosion(ctx);
+
+                    if self.player.is_dead() && killed_by.is_none() {
+                        killed_by = Some(String::from(enemy.label()));
+                    }
+                }
+            }
+
+            for enemy in self.enemies.iter() {
+                if enemy.laser().is_some_and(|laser| laser.hits_player(&self.player)) {
+                    self.player.take_damage(Laser::DAMAGE);
+                    if self.player.is_dead() && killed_by.is_none() {
+                        killed_by = Some(String::from(enemy.label()));
+                    }
+                }
+            }
+
+            for shot in self.enemy_shots.iter_mut() {
+                if self.player.graze(shot) {
+                    self.score.add(Self::GRAZE_POINTS);
+                }
+                if shot.collides_with_player(&self.player) {
+                    shot.is_alive = false;
+                    self.player.take_damage(1);
+                }
+            }
+            if self.player.is_dead() && killed_by.is_none() {
+                killed_by = Some(String::from("Enemy Fire"));
+            }
+            if (self.player.lives, self.player.health) < health_before {
+                self.damage_flash.trigger();
+            }
+            if self.player.lives < lives_before {
+                self.difficulty.performance.record_death();
+            }
+
+            self.enemy_shots.retain(|shot| shot.is_alive);
+            self.powerups.retain(|powerup| powerup.is_alive);
+            self.muzzle_flashes.retain(MuzzleFlash::is_alive);
+            self.score_popups.retain(ScorePopup::is_alive);
+            // Escapes were already recorded, so anything else that's gone was shot down, bombed or
+            // rammed the player. None of those got past, so they all count as cleared.
+            for enemy in self.enemies.iter().filter(|enemy| !enemy.is_alive) {
+                self.waves.record_kill(enemy.id());
+            }
             self.enemies.retain(|enemy| enemy.is_alive);
+
+            if let Some(killed_by) = killed_by {
+                self.end_game(&killed_by, ctx);
+            }
         }
 
         Ok(())
     }
 
     fn key_down_event(&mut self, ctx: &mut Context, input: keyboard::KeyInput, _repeat: bool) -> GameResult<()> {
-        match input.keycode {
-            Some(keyboard::KeyCode::Left) => self.input.move_left = true,
-            Some(keyboard::KeyCode::Right) => self.input.move_right = true,
-            Some(keyboard::KeyCode::Escape) => ctx.request_quit(),
+        let action = match input.keycode.and_then(|key| self.input_config.action_for(key)) {
+            Some(action) => action,
+            None => return Ok(()),
+        };
+
+        match action {
+            Action::MoveLeft => self.input.move_left = true,
+            Action::MoveRight => self.input.move_right = true,
+            Action::MoveUp => self.input.move_up = true,
+            Action::MoveDown => self.input.move_down = true,
+            _ => (), // Do nothing
+        }
+
+        // Letters are for typing in typing mode, so they shouldn't also set off a bomb or a toggle
+        if self.typing_mode && self.state == GameState::Playing && matches!(action, Action::Bomb | Action::ToggleMute | Action::ToggleAutofire) {
+            return Ok(());
+        }
+
+        match action {
+            Action::ToggleFps => self.show_fps = !self.show_fps,
+            Action::ToggleHitboxes => self.debug_draw = !self.debug_draw,
+            Action::ToggleMute => self.assets.toggle_mute(),
+
+            Action::ToggleAutofire => {
+                self.autofire = !self.autofire;
+                self.player.autofire = self.autofire;
+            },
+
+            Action::ToggleTyping => {
+                self.typing_mode = !self.typing_mode;
+                self.typing.clear();
+            },
+
+            Action::QuickSave if self.state == GameState::Playing || self.state == GameState::Paused => {
+                if let Err(e) = snapshot::save_state(ctx, &self.snapshot()) {
+                    eprintln!("Warning: couldn't save the game: {}", e);
+                }
+            },
+
+            Action::QuickLoad => {
+                if let Some(snapshot) = snapshot::load_state(ctx) {
+                    self.restore(snapshot, ctx)?;
+                    self.update_music(ctx);
+                    return Ok(());
+                }
+            },
+
             _ => (), // Do nothing
         }
 
+        if self.state == GameState::Playing && action == Action::Bomb {
+            self.input.bomb = true;
+        }
+
+        if self.state == GameState::Menu {
+            match action {
+                Action::MoveLeft => self.skin = self.skin.previous(),
+                Action::MoveRight => self.skin = self.skin.next(),
+                _ => (), // Do nothing
+            }
+        }
+
+        if self.state == GameState::Menu && action == Action::Pause {
+            ctx.request_quit();
+        }
+
+        let new_state = self.state.handle_action(action);
+        if self.state == GameState::Menu && new_state == GameState::Playing {
+            self.start_game();
+        }
+        self.state = new_state;
+        self.update_music(ctx);
+
+        Ok(())
+    }
+
+    // Gameplay keeps its own resolution, the window just shows it bigger or smaller
+    fn resize_event(&mut self, _ctx: &mut Context, width: f32, height: f32) -> GameResult<()> {
+        self.viewport.resize(width, height);
+        Ok(())
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> GameResult<()> {
+        if !self.typing_mode || self.state != GameState::Playing {
+            return Ok(());
+        }
+
+        if let Keystroke::Completed { target } = self.typing.type_char(character, &self.enemies) {
+            if let Some(index) = self.enemies.iter().position(|enemy| enemy.id() == target) {
+                self.enemies[index].is_alive = false;
+                self.enemy_destroyed(index, ctx);
+            }
+        }
+
         Ok(())
     }
 
     fn key_up_event(&mut self, _ctx: &mut Context, input: keyboard::KeyInput) -> GameResult<()> {
-        match input.keycode {
-            Some(keyboard::KeyCode::Left) => self.input.move_left = false,
-            Some(keyboard::KeyCode::Right) => self.input.move_right = false,
+        match input.keycode.and_then(|key| self.input_config.action_for(key)) {
+            Some(Action::MoveLeft) => self.input.move_left = false,
+            Some(Action::MoveRight) => self.input.move_right = false,
+            Some(Action::MoveUp) => self.input.move_up = false,
+            Some(Action::MoveDown) => self.input.move_down = false,
             _ => (), // Do nothing
         }
 
         Ok(())
     }
 
-    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
-        let dark_blue = graphics::Color::from_rgb(26, 51, 77);
-        let mut canvas = graphics::Canvas::from_frame(ctx, dark_blue);
+    fn gamepad_button_down_event(&mut self, _ctx: &mut Context, btn: gilrs::Button, _id: GamepadId) -> GameResult<()> {
+        if btn == gilrs::Button::South {
+            self.input.gamepad_fire = true;
+        }
 
-        if self.game_over {
-            let mut text = graphics::Text::new(format!("Killed by {}.\nScore: {}", self.killed_by, self.score));
-            text.set_font("MainFont");
-            text.set_scale(graphics::PxScale::from(40.0));
+        Ok(())
+    }
 
-            let top_left = Point2 {
-                x: (self.screen_width - text.dimensions(ctx).unwrap().w) / 2.0,
-                y: (self.screen_height - text.dimensions(ctx).unwrap().h) / 2.0,
-            };
-            canvas.draw(&text, graphics::DrawParam::default().dest(top_left));
-            canvas.finish(ctx)?;
-            return Ok(())
+    fn gamepad_button_up_event(&mut self, _ctx: &mut Context, btn: gilrs::Button, _id: GamepadId) -> GameResult<()> {
+        if btn == gilrs::Button::South {
+            self.input.gamepad_fire = false;
         }
 
-        self.player.draw(&mut canvas, &self.assets);
+        Ok(())
+    }
 
-        for shot in self.shots.iter_mut() {
-            shot.draw(&mut canvas, &self.assets);
+    fn gamepad_axis_event(&mut self, _ctx: &mut Context, axis: gilrs::Axis, value: f32, _id: GamepadId) -> GameResult<()> {
+        match axis {
+            gilrs::Axis::LeftStickX => self.input.stick.x = input::apply_deadzone(value),
+            // Up on the stick is positive, but down the screen is positive
+            gilrs::Axis::LeftStickY => self.input.stick.y = -input::apply_deadzone(value),
+            _ => (), // Do nothing
         }
 
-        for enemy in self.enemies.iter_mut() {
-            enemy.draw(&mut canvas);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let dark_blue = graphics::Color::from_rgb(26, 51, 77);
+        let mut canvas = graphics::Canvas::from_frame(ctx, Viewport::BAR_COLOR);
+        canvas.set_screen_coordinates(self.viewport.letterbox(self.full_screen()));
+        canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(self.full_screen()).color(dark_blue));
+
+        match self.state {
+            GameState::Menu => {
+                let autofire = if self.autofire { "On" } else { "Off" };
+                let label = format!("Press Enter to start\n\n< Ship: {} >\nAutofire: {}", self.skin.name(), autofire);
+                self.draw_centered_text(&label, &mut canvas, ctx);
+            },
+
+            GameState::GameOver => {
+                let label = format!("Killed by {}.\nScore: {}\nHigh score: {}\n\nPress Enter to continue",
+                    self.killed_by, self.score.current, self.score.high);
+                self.draw_centered_text(&label, &mut canvas, ctx);
+            },
+
+            GameState::Playing | GameState::Paused => self.draw_game(&mut canvas, ctx)?,
         }
 
-        if debug::is_active() {
-            for enemy in &mut self.enemies {
-                debug::draw_outline(enemy.bounding_rect(ctx), &mut canvas, ctx).unwrap();
-            }
+        // Anything that strayed off the virtual screen, like from shaking, ends up under the bars
+        canvas.set_screen_coordinates(self.viewport.letterbox(self.full_screen()));
+        self.viewport.draw_bars(&mut canvas);
+
+        if self.show_fps {
+            hud::draw_fps(&mut canvas, ctx.time.fps(), self.screen.height);
         }
 
         canvas.finish(ctx)?;
@@ -243,6 +970,7 @@ pub fn main() {
         window_mode(WindowMode {
             width: 1200.0,
             height: 1000.0,
+            resizable: true,
             ..Default::default()
         });
     let (mut ctx, event_loop) = ContextBuilder::new("shooter", "Andrew").
@@ -258,10 +986,15 @@ pub fn main() {
         ctx.fs.mount(&path, true);
     }
 
-    let font_data = graphics::FontData::from_path(&ctx, "/DejaVuSerif.ttf").unwrap();
-    ctx.gfx.add_font("MainFont", font_data);
+    let mut state = MainState::new(&mut ctx, &conf, rng::seed_from_env()).unwrap();
 
-    let state = MainState::new(&mut ctx, &conf).unwrap();
+    // Set REPLAY to play back the last recorded game instead of starting at the menu
+    if env::var("REPLAY").is_ok() {
+        match Replay::load(&ctx) {
+            Some(replay) => state.start_replay(replay, &mut ctx),
+            None => eprintln!("Warning: couldn't load {}", Replay::PATH),
+        }
+    }
 
     event::run(ctx, event_loop, state);
 }
@@ -0,0 +1,98 @@
+use ggez::mint::{Point2, Vector2};
+use std::f32::consts::PI;
+use std::fmt::Debug;
+
+// Movement patterns describe how far an enemy has strayed from the path its velocity would take it
+// on, given how long it has been alive
+pub trait MovementPattern: Debug {
+    fn offset(&self, elapsed: f32) -> Vector2<f32>;
+
+    // Extra velocity for patterns that react to the player, given the centers of the enemy and
+    // the player it's chasing
+    fn steer(&mut self, _pos: Point2<f32>, _target: Point2<f32>, _seconds: f32) -> Vector2<f32> {
+        Vector2 { x: 0.0, y: 0.0 }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StraightDown;
+
+impl MovementPattern for StraightDown {
+    fn offset(&self, _elapsed: f32) -> Vector2<f32> {
+        Vector2 { x: 0.0, y: 0.0 }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SineWave {
+    pub amplitude: f32,
+    pub period: f32,
+}
+
+impl MovementPattern for SineWave {
+    fn offset(&self, elapsed: f32) -> Vector2<f32> {
+        Vector2 {
+            x: self.amplitude * f32::sin(2.0 * PI * elapsed / self.period),
+            y: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Zigzag {
+    pub amplitude: f32,
+    pub period: f32,
+}
+
+impl MovementPattern for Zigzag {
+    fn offset(&self, elapsed: f32) -> Vector2<f32> {
+        // Triangle wave that starts at zero and goes right first, like the sine wave
+        let phase = (elapsed / self.period + 0.25).rem_euclid(1.0);
+        let triangle = 4.0 * f32::abs(phase - 0.5) - 1.0;
+
+        Vector2 {
+            x: -self.amplitude * triangle,
+            y: 0.0,
+        }
+    }
+}
+
+// Drifts sideways toward the player while the velocity carries it down. The drift speeds up
+// gradually and is capped, so the enemy can't just follow the player's every move.
+#[derive(Debug, Clone)]
+pub struct SeekPlayer {
+    pub max_speed: f32,
+    pub acceleration: f32,
+    drift: f32,
+}
+
+impl Default for SeekPlayer {
+    fn default() -> Self {
+        SeekPlayer::new(Self::MAX_SPEED, Self::ACCELERATION)
+    }
+}
+
+impl SeekPlayer {
+    pub const MAX_SPEED: f32 = 80.0;
+    pub const ACCELERATION: f32 = 60.0;
+    // Drift speed per pixel of distance, so it eases in instead of overshooting
+    const GAIN: f32 = 2.0;
+
+    pub fn new(max_speed: f32, acceleration: f32) -> Self {
+        SeekPlayer { max_speed, acceleration, drift: 0.0 }
+    }
+}
+
+impl MovementPattern for SeekPlayer {
+    fn offset(&self, _elapsed: f32) -> Vector2<f32> {
+        Vector2 { x: 0.0, y: 0.0 }
+    }
+
+    fn steer(&mut self, pos: Point2<f32>, target: Point2<f32>, seconds: f32) -> Vector2<f32> {
+        let wanted = f32::clamp((target.x - pos.x) * Self::GAIN, -self.max_speed, self.max_speed);
+        let max_change = self.acceleration * seconds;
+        self.drift += f32::clamp(wanted - self.drift, -max_change, max_change);
+
+        Vector2 { x: self.drift, y: 0.0 }
+    }
+}
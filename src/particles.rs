@@ -0,0 +1,218 @@
+use ggez::graphics;
+use ggez::mint::{Point2, Vector2};
+use rand::Rng;
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone)]
+pub struct Particle {
+    pub pos: Point2<f32>,
+    pub velocity: Vector2<f32>,
+    pub lifetime: f32,
+    pub color: graphics::Color,
+    max_lifetime: f32,
+}
+
+impl Particle {
+    pub const SIZE: f32 = 4.0;
+
+    pub fn new(pos: Point2<f32>, velocity: Vector2<f32>, lifetime: f32, color: graphics::Color) -> Self {
+        Particle {
+            pos, velocity, lifetime, color,
+            max_lifetime: lifetime,
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.lifetime > 0.0
+    }
+
+    // Fades out linearly over the particle's lifetime
+    pub fn alpha(&self) -> f32 {
+        f32::max(self.lifetime / self.max_lifetime, 0.0)
+    }
+
+    pub fn update(&mut self, seconds: f32) {
+        self.pos.x += self.velocity.x * seconds;
+        self.pos.y += self.velocity.y * seconds;
+        self.lifetime -= seconds;
+    }
+
+    pub fn draw(&self, canvas: &mut graphics::Canvas) {
+        let rect = graphics::Rect::new(
+            self.pos.x - Self::SIZE / 2.0, self.pos.y - Self::SIZE / 2.0,
+            Self::SIZE, Self::SIZE);
+        let color = graphics::Color { a: self.alpha(), ..self.color };
+
+        canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(rect).color(color));
+    }
+}
+
+// A quick flare at the gun when the player fires. It points up the way the shots go, stretching
+// out and fading away to nothing over its lifetime.
+#[derive(Debug, Clone)]
+pub struct MuzzleFlash {
+    pub pos: Point2<f32>,
+    pub lifetime: f32,
+}
+
+impl MuzzleFlash {
+    pub const LIFETIME: f32 = 0.08;
+    pub const START_SIZE: f32 = 10.0;
+    pub const END_SIZE: f32 = 30.0;
+    pub const COLOR: graphics::Color = graphics::Color::new(1.0, 0.95, 0.7, 1.0);
+
+    pub fn new(pos: Point2<f32>) -> Self {
+        MuzzleFlash { pos, lifetime: Self::LIFETIME }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.lifetime > 0.0
+    }
+
+    // Goes from 0 when it appears to 1 when it's gone
+    pub fn progress(&self) -> f32 {
+        (1.0 - self.lifetime / Self::LIFETIME).clamp(0.0, 1.0)
+    }
+
+    pub fn alpha(&self) -> f32 {
+        1.0 - self.progress()
+    }
+
+    pub fn size(&self) -> f32 {
+        Self::START_SIZE + (Self::END_SIZE - Self::START_SIZE) * self.progress()
+    }
+
+    pub fn update(&mut self, seconds: f32) {
+        self.lifetime -= seconds;
+    }
+
+    // Twice as long as it is wide, with its bottom edge at the gun
+    pub fn draw(&self, canvas: &mut graphics::Canvas) {
+        let size = self.size();
+        let rect = graphics::Rect::new(self.pos.x - size / 4.0, self.pos.y - size, size / 2.0, size);
+        let color = graphics::Color { a: self.alpha(), ..Self::COLOR };
+
+        canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(rect).color(color));
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Explosion {
+    pub particle_count: usize,
+    pub max_speed: f32,
+    pub lifetime: f32,
+    pub color: graphics::Color,
+}
+
+impl Default for Explosion {
+    fn default() -> Self {
+        Explosion {
+            particle_count: 30,
+            max_speed: 250.0,
+            lifetime: 0.6,
+            color: graphics::Color::from_rgb(255, 180, 60),
+        }
+    }
+}
+
+impl Explosion {
+    pub fn burst<R: Rng>(&self, pos: Point2<f32>, rng: &mut R) -> Vec<Particle> {
+        (0 .. self.particle_count).map(|_| self.particle(pos, rng)).collect()
+    }
+
+    // One particle flying off in a random direction
+    pub fn particle<R: Rng>(&self, pos: Point2<f32>, rng: &mut R) -> Particle {
+        let angle = rng.gen_range(0.0 .. 2.0 * PI);
+        let speed = rng.gen_range(0.0 .. self.max_speed);
+        let velocity = Vector2 { x: speed * angle.cos(), y: speed * angle.sin() };
+        let lifetime = rng.gen_range(self.lifetime / 2.0 .. self.lifetime);
+
+        Particle::new(pos, velocity, lifetime, self.color)
+    }
+}
+
+// Every particle in the game, in a fixed number of slots that get reused once their particle dies.
+// Once they're all taken, new particles are dropped, so a pile of explosions at once can't blow up
+// the frame time.
+#[derive(Debug, Clone)]
+pub struct ParticleSystem {
+    slots: Vec<Particle>,
+    explosion: Explosion,
+}
+
+impl ParticleSystem {
+    pub fn new(capacity: usize) -> Self {
+        let dead = Particle::new(Point2 { x: 0.0, y: 0.0 }, Vector2 { x: 0.0, y: 0.0 }, 0.0, graphics::Color::WHITE);
+
+        ParticleSystem {
+            slots: vec![dead; capacity],
+            explosion: Explosion::default(),
+        }
+    }
+
+    // What the bursts look like
+    pub fn with_explosion(mut self, explosion: Explosion) -> Self {
+        self.explosion = explosion;
+        self
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    // Returns false if every slot is taken
+    pub fn emit(&mut self, particle: Particle) -> bool {
+        match self.slots.iter().position(|slot| !slot.is_alive()) {
+            Some(slot) => {
+                self.slots[slot] = particle;
+                true
+            },
+            None => false,
+        }
+    }
+
+    // Returns how many particles were actually emitted
+    pub fn emit_burst<R: Rng>(&mut self, pos: Point2<f32>, count: usize, rng: &mut R) -> usize {
+        let mut emitted = 0;
+        for slot in self.slots.iter_mut().filter(|slot| !slot.is_alive()).take(count) {
+            *slot = self.explosion.particle(pos, rng);
+            emitted += 1;
+        }
+        emitted
+    }
+
+    // A burst with the explosion's own particle count
+    pub fn explode<R: Rng>(&mut self, pos: Point2<f32>, rng: &mut R) -> usize {
+        self.emit_burst(pos, self.explosion.particle_count, rng)
+    }
+
+    pub fn update(&mut self, seconds: f32) {
+        for particle in self.iter_mut() {
+            particle.update(seconds);
+        }
+    }
+
+    pub fn draw(&self, canvas: &mut graphics::Canvas) {
+        for particle in self.iter() {
+            particle.draw(canvas);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for particle in &mut self.slots {
+            particle.lifetime = 0.0;
+        }
+    }
+
+    pub fn live_count(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.slots.iter().filter(|particle| particle.is_alive())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Particle> {
+        self.slots.iter_mut().filter(|particle| particle.is_alive())
+    }
+}
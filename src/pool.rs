@@ -0,0 +1,63 @@
+use ggez::mint::{Point2, Vector2};
+
+use crate::entities::Shot;
+
+// Fixed number of shot slots that get reused once their shot dies, so firing doesn't allocate
+#[derive(Debug, Clone)]
+pub struct ShotPool {
+    slots: Vec<Shot>,
+}
+
+impl ShotPool {
+    pub fn new(capacity: usize) -> Self {
+        let mut dead = Shot::new(Point2 { x: 0.0, y: 0.0 }, Vector2 { x: 0.0, y: 0.0 });
+        dead.is_alive = false;
+
+        ShotPool {
+            slots: vec![dead; capacity],
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    // Returns the slot the shot went into, or None if every slot is taken
+    pub fn spawn(&mut self, pos: Point2<f32>, velocity: Vector2<f32>) -> Option<usize> {
+        self.add(Shot::new(pos, velocity))
+    }
+
+    pub fn add(&mut self, shot: Shot) -> Option<usize> {
+        let slot = self.slots.iter().position(|slot| !slot.is_alive)?;
+        self.slots[slot] = shot;
+        Some(slot)
+    }
+
+    pub fn get(&self, slot: usize) -> Option<&Shot> {
+        self.slots.get(slot).filter(|shot| shot.is_alive)
+    }
+
+    pub fn update_all(&mut self, seconds: f32) {
+        for shot in self.iter_mut() {
+            shot.update(seconds);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for shot in &mut self.slots {
+            shot.is_alive = false;
+        }
+    }
+
+    pub fn live_count(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Shot> {
+        self.slots.iter().filter(|shot| shot.is_alive)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Shot> {
+        self.slots.iter_mut().filter(|shot| shot.is_alive)
+    }
+}
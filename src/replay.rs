@@ -0,0 +1,115 @@
+use ggez::mint::Vector2;
+use ggez::{Context, GameError, GameResult};
+use serde::{Deserialize, Serialize};
+
+use crate::assets::read_text_file;
+
+// The input that drove one gameplay tick. A game started from the same seed and fed the same
+// frames plays out the same way. Quick saves, quick loads and typing mode aren't recorded, so
+// replays of games using them will drift.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrameInput {
+    pub move_x: f32,
+    pub move_y: f32,
+    pub fire: bool,
+    pub bomb: bool,
+}
+
+impl FrameInput {
+    pub fn new(move_amount: Vector2<f32>, fire: bool, bomb: bool) -> Self {
+        FrameInput { move_x: move_amount.x, move_y: move_amount.y, fire, bomb }
+    }
+
+    pub fn move_amount(&self) -> Vector2<f32> {
+        Vector2 { x: self.move_x, y: self.move_y }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub frames: Vec<FrameInput>,
+}
+
+impl Replay {
+    pub const PATH: &'static str = "/replay.json";
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Replay, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn save(&self, ctx: &Context) -> GameResult<()> {
+        let file = ctx.fs.create(Self::PATH)?;
+        serde_json::to_writer(file, self).
+            map_err(|e| GameError::FilesystemError(e.to_string()))
+    }
+
+    // None if there's no replay or it can't be read, with a warning for the latter
+    pub fn load(ctx: &Context) -> Option<Replay> {
+        let json = read_text_file(ctx, Self::PATH)?;
+
+        match Replay::from_json(&json) {
+            Ok(replay) => Some(replay),
+            Err(e) => {
+                eprintln!("Warning: ignoring {}: {}", Self::PATH, e);
+                None
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    replay: Replay,
+}
+
+impl Recorder {
+    pub fn new(seed: u64) -> Self {
+        Recorder { replay: Replay { seed, frames: Vec::new() } }
+    }
+
+    pub fn record(&mut self, frame: FrameInput) {
+        self.replay.frames.push(frame);
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    pub fn finish(self) -> Replay {
+        self.replay
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Replayer {
+    replay: Replay,
+    next_frame: usize,
+}
+
+impl Replayer {
+    pub fn new(replay: Replay) -> Self {
+        Replayer { replay, next_frame: 0 }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.replay.seed
+    }
+
+    // None once every recorded frame has been played
+    pub fn next_frame(&mut self) -> Option<FrameInput> {
+        let frame = self.replay.frames.get(self.next_frame).copied();
+        if frame.is_some() {
+            self.next_frame += 1;
+        }
+        frame
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.replay.frames.len()
+    }
+}
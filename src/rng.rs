@@ -0,0 +1,16 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+// All randomness in a game comes from one seed, so a run can be reproduced by reusing it.
+// Set SEED to pick one, otherwise it's random.
+pub fn seed_from_env() -> u64 {
+    std::env::var("SEED").ok().
+        and_then(|seed| seed.parse().ok()).
+        unwrap_or_else(|| rand::thread_rng().gen())
+}
+
+// Gives a system its own generator derived from the game's, so it doesn't matter how much
+// randomness the other systems use up between ticks
+pub fn fork(rng: &mut StdRng) -> StdRng {
+    StdRng::seed_from_u64(rng.gen())
+}
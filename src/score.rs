@@ -0,0 +1,126 @@
+#[derive(Debug, Default, Clone)]
+pub struct Score {
+    pub current: u32,
+    pub high: u32,
+}
+
+impl Score {
+    pub fn new() -> Self {
+        Score::default()
+    }
+
+    pub fn add(&mut self, points: u32) {
+        self.current += points;
+        self.high = u32::max(self.high, self.current);
+    }
+
+    pub fn reset(&mut self) {
+        self.current = 0;
+    }
+}
+
+// Kills in quick succession are worth more. Each kill within `COMBO_WINDOW` of the last one bumps
+// the multiplier, up to `MAX_MULTIPLIER`.
+#[derive(Debug, Clone)]
+pub struct Combo {
+    multiplier: u32,
+    time_left: f32,
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Combo { multiplier: 1, time_left: 0.0 }
+    }
+}
+
+impl Combo {
+    pub const COMBO_WINDOW: f32 = 1.5;
+    pub const MAX_MULTIPLIER: u32 = 8;
+
+    pub fn new() -> Self {
+        Combo::default()
+    }
+
+    pub fn multiplier(&self) -> u32 {
+        self.multiplier
+    }
+
+    // Returns the multiplier for this kill
+    pub fn register_kill(&mut self) -> u32 {
+        if self.time_left > 0.0 {
+            self.multiplier = u32::min(self.multiplier + 1, Self::MAX_MULTIPLIER);
+        } else {
+            self.multiplier = 1;
+        }
+
+        self.time_left = Self::COMBO_WINDOW;
+        self.multiplier
+    }
+
+    pub fn decay(&mut self, seconds: f32) {
+        self.time_left = f32::max(self.time_left - seconds, 0.0);
+        if self.time_left <= 0.0 {
+            self.multiplier = 1;
+        }
+    }
+}
+
+// Splits the game into waves of `WAVE_LENGTH` seconds. If every enemy spawned during a wave has
+// been destroyed by the time the next one starts, and none got past the player, the wave was a
+// perfect clear. Shards from a split enemy count as spawns of their own.
+#[derive(Debug, Clone)]
+pub struct WaveTracker {
+    time_left: f32,
+    spawned: u32,
+    // Enemies from this wave that are still around
+    pending: Vec<u64>,
+    escaped: bool,
+}
+
+impl Default for WaveTracker {
+    fn default() -> Self {
+        WaveTracker { time_left: Self::WAVE_LENGTH, spawned: 0, pending: Vec::new(), escaped: false }
+    }
+}
+
+impl WaveTracker {
+    pub const WAVE_LENGTH: f32 = 20.0;
+    pub const PERFECT_CLEAR_BONUS: u32 = 100;
+
+    pub fn new() -> Self {
+        WaveTracker::default()
+    }
+
+    pub fn record_spawn(&mut self, id: u64) {
+        self.spawned += 1;
+        self.pending.push(id);
+    }
+
+    // Enemies from earlier waves are ignored
+    pub fn record_kill(&mut self, id: u64) {
+        self.pending.retain(|&pending| pending != id);
+    }
+
+    pub fn record_escape(&mut self, id: u64) {
+        if self.pending.contains(&id) {
+            self.escaped = true;
+            self.record_kill(id);
+        }
+    }
+
+    pub fn is_perfect(&self) -> bool {
+        self.spawned > 0 && self.pending.is_empty() && !self.escaped
+    }
+
+    // Returns the bonus for a perfect clear when the next wave starts
+    pub fn update(&mut self, seconds: f32) -> Option<u32> {
+        self.time_left -= seconds;
+        if self.time_left > 0.0 {
+            return None;
+        }
+
+        let perfect = self.is_perfect();
+        *self = WaveTracker { time_left: self.time_left + Self::WAVE_LENGTH, ..WaveTracker::default() };
+        perfect.then_some(Self::PERFECT_CLEAR_BONUS)
+    }
+}
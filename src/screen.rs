@@ -0,0 +1,11 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenConfig {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl ScreenConfig {
+    pub fn new(width: f32, height: f32) -> Self {
+        ScreenConfig { width, height }
+    }
+}
@@ -0,0 +1,160 @@
+use ggez::mint::{Point2, Vector2};
+use ggez::{Context, GameError, GameResult};
+use serde::{Deserialize, Serialize};
+
+use crate::assets::{read_text_file, Assets, Sprite, TextSprite};
+use crate::entities::{Enemy, Player, Shot};
+
+// Saved games only store plain data. Sprites can't be saved, so enemies keep their label and get
+// a fresh text sprite for it when loaded. Movement patterns aren't kept either, loaded enemies
+// just fly straight.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub x: f32,
+    pub y: f32,
+    pub health: i32,
+    pub lives: u32,
+    pub bombs: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnemySnapshot {
+    pub label: String,
+    pub x: f32,
+    pub y: f32,
+    // Saves made before enemies could move sideways only have the downward speed
+    #[serde(default)]
+    pub velocity_x: f32,
+    #[serde(alias = "speed")]
+    pub velocity_y: f32,
+    pub health: u32,
+    // Missing from saves made before enemies had health bars
+    #[serde(default)]
+    pub max_health: Option<u32>,
+    pub point_value: u32,
+    pub fire_interval: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShotSnapshot {
+    pub x: f32,
+    pub y: f32,
+    pub velocity_x: f32,
+    pub velocity_y: f32,
+    pub damage: u32,
+    pub scale: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub player: PlayerSnapshot,
+    pub enemies: Vec<EnemySnapshot>,
+    pub shots: Vec<ShotSnapshot>,
+    pub enemy_shots: Vec<ShotSnapshot>,
+    pub score: u32,
+    pub elapsed: f32,
+}
+
+impl PlayerSnapshot {
+    pub fn capture(player: &Player) -> Self {
+        PlayerSnapshot {
+            x: player.pos.x,
+            y: player.pos.y,
+            health: player.health,
+            lives: player.lives,
+            bombs: player.bombs,
+        }
+    }
+
+    pub fn restore(&self, player: &mut Player) {
+        player.pos = Point2 { x: self.x, y: self.y };
+        player.health = self.health;
+        player.lives = self.lives;
+        player.bombs = self.bombs;
+    }
+}
+
+impl EnemySnapshot {
+    pub fn capture(enemy: &Enemy) -> Self {
+        EnemySnapshot {
+            label: String::from(enemy.label()),
+            x: enemy.pos.x,
+            y: enemy.pos.y,
+            velocity_x: enemy.base_velocity().x,
+            velocity_y: enemy.base_velocity().y,
+            health: enemy.health,
+            max_health: Some(enemy.max_health()),
+            point_value: enemy.point_value,
+            fire_interval: enemy.fire_interval(),
+        }
+    }
+
+    pub fn into_enemy(self, ctx: &mut Context) -> GameResult<Enemy> {
+        let sprite = Box::new(TextSprite::new(&self.label, Assets::MAIN_FONT, ctx)?.with_shadow());
+        self.into_enemy_with_sprite(sprite)
+    }
+
+    pub fn into_enemy_with_sprite(self, sprite: Box<dyn Sprite>) -> GameResult<Enemy> {
+        let pos = Point2 { x: self.x, y: self.y };
+        let velocity = Vector2 { x: self.velocity_x, y: self.velocity_y };
+        let mut enemy = Enemy::new(&self.label, pos, self.velocity_y, self.health, self.point_value, sprite)?.
+            with_velocity(velocity).
+            with_max_health(self.max_health.unwrap_or(self.health));
+
+        if let Some(fire_interval) = self.fire_interval {
+            enemy = enemy.with_fire_interval(fire_interval);
+        }
+
+        Ok(enemy)
+    }
+}
+
+impl ShotSnapshot {
+    pub fn capture(shot: &Shot) -> Self {
+        ShotSnapshot {
+            x: shot.pos.x,
+            y: shot.pos.y,
+            velocity_x: shot.velocity().x,
+            velocity_y: shot.velocity().y,
+            damage: shot.damage,
+            scale: shot.scale,
+        }
+    }
+
+    pub fn into_shot(self) -> Shot {
+        let pos = Point2 { x: self.x, y: self.y };
+        let velocity = Vector2 { x: self.velocity_x, y: self.velocity_y };
+        Shot::new(pos, velocity).with_power(self.damage, self.scale)
+    }
+}
+
+impl GameSnapshot {
+    const PATH: &'static str = "/savegame.json";
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<GameSnapshot, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+pub fn save_state(ctx: &Context, snapshot: &GameSnapshot) -> GameResult<()> {
+    let file = ctx.fs.create(GameSnapshot::PATH)?;
+    serde_json::to_writer(file, snapshot).
+        map_err(|e| GameError::FilesystemError(e.to_string()))
+}
+
+// None if there's no saved game or it can't be read, with a warning for the latter
+pub fn load_state(ctx: &Context) -> Option<GameSnapshot> {
+    let json = read_text_file(ctx, GameSnapshot::PATH)?;
+
+    match GameSnapshot::from_json(&json) {
+        Ok(snapshot) => Some(snapshot),
+        Err(e) => {
+            eprintln!("Warning: ignoring {}: {}", GameSnapshot::PATH, e);
+            None
+        },
+    }
+}
@@ -0,0 +1,243 @@
+use ggez::graphics;
+use ggez::mint::{Point2, Vector2};
+use ggez::{Context, GameResult};
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use crate::assets::{Assets, TextSprite};
+use crate::difficulty::Difficulty;
+use crate::entities::{Enemy, EnemyKind};
+use crate::movement::SeekPlayer;
+use crate::words::WordList;
+
+// Which side of the screen an enemy comes in from. Enemies from the sides head down and inwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnEdge {
+    Top,
+    Left,
+    Right,
+}
+
+impl SpawnEdge {
+    // Radians away from straight down for enemies coming in from the sides. Shallow enough that
+    // they're still on screen when they reach the bottom.
+    pub const SIDE_ANGLE: f32 = 0.5;
+
+    pub fn velocity(self, speed: f32) -> Vector2<f32> {
+        let (sin, cos) = Self::SIDE_ANGLE.sin_cos();
+        match self {
+            SpawnEdge::Top => Vector2 { x: 0.0, y: speed },
+            SpawnEdge::Left => Vector2 { x: speed * sin, y: speed * cos },
+            SpawnEdge::Right => Vector2 { x: -speed * sin, y: speed * cos },
+        }
+    }
+}
+
+// Everything needed to create an enemy, decided without touching the graphics context
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spawn {
+    pub kind: EnemyKind,
+    pub label: String,
+    pub pos: Point2<f32>,
+    pub speed: f32,
+    pub fire_interval: Option<f32>,
+    pub seeks_player: bool,
+    pub edge: SpawnEdge,
+}
+
+impl Spawn {
+    pub fn into_enemy(self, ctx: &mut Context) -> GameResult<Enemy> {
+        let sprite = Box::new(TextSprite::new(&self.label, Assets::MAIN_FONT, ctx)?.with_shadow());
+        let enemy = self.kind.build(&self.label, self.pos, self.speed, sprite)?.
+            with_velocity(self.edge.velocity(self.speed)).
+            with_entry_duration(Enemy::ENTRY_DURATION);
+        let mut enemy = self.kind.with_abilities(enemy, ctx)?;
+
+        if let Some(fire_interval) = self.fire_interval {
+            enemy = enemy.with_fire_interval(fire_interval);
+        }
+        if self.seeks_player {
+            enemy = enemy.with_movement(Box::new(SeekPlayer::default()));
+        }
+
+        Ok(enemy)
+    }
+}
+
+// Blinks at the top of the screen where a spawn is about to come in, until the spawn is due
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnWarning {
+    pub spawn: Spawn,
+    pub timer: f32,
+}
+
+impl SpawnWarning {
+    pub const WIDTH: f32 = 40.0;
+    pub const HEIGHT: f32 = 12.0;
+    pub const BLINKS_PER_SECOND: f32 = 4.0;
+    pub const COLOR: graphics::Color = graphics::Color::new(1.0, 0.3, 0.1, 0.9);
+
+    pub fn new(spawn: Spawn, lead_time: f32) -> Self {
+        SpawnWarning { spawn, timer: lead_time }
+    }
+
+    pub fn pos(&self) -> Point2<f32> {
+        self.spawn.pos
+    }
+
+    pub fn update(&mut self, seconds: f32) {
+        self.timer -= seconds;
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.timer <= 0.0
+    }
+
+    // On for the first half of every blink
+    pub fn is_visible(&self) -> bool {
+        (self.timer * Self::BLINKS_PER_SECOND * 2.0).ceil() as i32 % 2 == 0
+    }
+
+    pub fn draw(&self, canvas: &mut graphics::Canvas) {
+        if !self.is_visible() {
+            return;
+        }
+
+        let rect = graphics::Rect::new(self.spawn.pos.x, 0.0, Self::WIDTH, Self::HEIGHT);
+        canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(rect).color(Self::COLOR));
+    }
+}
+
+#[derive(Debug)]
+pub struct Spawner {
+    rng: StdRng,
+    screen_width: f32,
+    time_until_next_enemy: f32,
+    words: WordList,
+    warnings: Vec<SpawnWarning>,
+    warning_lead_time: f32,
+    side_spawn_chance: f64,
+}
+
+impl Spawner {
+    pub const ENEMIES: [&'static str; 7] = [
+        "Segfaults", "Undefined Behaviour",
+        "NULLs", "Inefficiencies", "Bloat",
+        "Unnecessary Heap\nAllocations", "Data Races"
+    ];
+
+    // Keeps spawns far enough from the right edge that the label stays on screen
+    pub const SPAWN_MARGIN: f32 = 100.0;
+    pub const FIRST_SPAWN_DELAY: f32 = 1.0;
+    pub const SHOOTER_CHANCE: f64 = 0.25;
+    pub const SHOOTER_FIRE_INTERVAL: f32 = 2.0;
+    pub const TANK_CHANCE: f64 = 0.1;
+    pub const FAST_CHANCE: f64 = 0.2;
+    pub const SPLITTER_CHANCE: f64 = 0.1;
+    pub const SEEKER_CHANCE: f64 = 0.15;
+    // How long fast enemies are warned about before they come in
+    pub const WARNING_LEAD_TIME: f32 = 1.0;
+    pub const SIDE_SPAWN_CHANCE: f64 = 0.2;
+    // Enemies from the sides come in somewhere above this, out of the player's reach
+    pub const SIDE_SPAWN_MAX_Y: f32 = 200.0;
+
+    pub fn new(screen_width: f32, rng: StdRng) -> Self {
+        Spawner {
+            rng, screen_width,
+            time_until_next_enemy: Self::FIRST_SPAWN_DELAY,
+            words: WordList::default(),
+            warnings: Vec::new(),
+            warning_lead_time: Self::WARNING_LEAD_TIME,
+            side_spawn_chance: 0.0,
+        }
+    }
+
+    // Everything comes in from the top unless this is set
+    pub fn with_side_spawn_chance(mut self, chance: f64) -> Self {
+        self.side_spawn_chance = chance;
+        self
+    }
+
+    pub fn with_warning_lead_time(mut self, seconds: f32) -> Self {
+        self.warning_lead_time = seconds;
+        self
+    }
+
+    // Spawns that have been announced but haven't come in yet
+    pub fn warnings(&self) -> &[SpawnWarning] {
+        &self.warnings
+    }
+
+    pub fn with_words(mut self, words: WordList) -> Self {
+        self.words = words;
+        self
+    }
+
+    pub fn tick(&mut self, seconds: f32, difficulty: &Difficulty, ctx: &mut Context) -> GameResult<Vec<Enemy>> {
+        self.next_spawns(seconds, difficulty).
+            into_iter().
+            map(|spawn| spawn.into_enemy(ctx)).
+            collect()
+    }
+
+    pub fn next_spawns(&mut self, seconds: f32, difficulty: &Difficulty) -> Vec<Spawn> {
+        let mut spawns = Vec::new();
+
+        for warning in self.warnings.iter_mut() {
+            warning.update(seconds);
+        }
+        let (expired, waiting) = self.warnings.drain(..).partition(SpawnWarning::is_expired);
+        self.warnings = waiting;
+        spawns.extend(expired.into_iter().map(|warning: SpawnWarning| warning.spawn));
+
+        self.time_until_next_enemy -= seconds;
+        while self.time_until_next_enemy <= 0.0 {
+            // Fast enemies are hard to react to, so they get announced first
+            let spawn = self.random_spawn(difficulty);
+            if spawn.kind == EnemyKind::Fast && self.warning_lead_time > 0.0 {
+                self.warnings.push(SpawnWarning::new(spawn, self.warning_lead_time));
+            } else {
+                spawns.push(spawn);
+            }
+
+            let spawn_interval = difficulty.enemy_spawn_interval();
+            self.time_until_next_enemy += self.rng.gen_range(spawn_interval * 0.5 .. spawn_interval * 1.5);
+        }
+
+        spawns
+    }
+
+    fn random_spawn(&mut self, difficulty: &Difficulty) -> Spawn {
+        let max_x = f32::max(self.screen_width - Self::SPAWN_MARGIN, 1.0);
+        let edge = if self.side_spawn_chance > 0.0 && self.rng.gen_bool(self.side_spawn_chance) {
+            if self.rng.gen_bool(0.5) { SpawnEdge::Left } else { SpawnEdge::Right }
+        } else {
+            SpawnEdge::Top
+        };
+        let pos = match edge {
+            SpawnEdge::Top => Point2 { x: self.rng.gen_range(0.0 .. max_x), y: 0.0 },
+            SpawnEdge::Left => Point2 { x: 0.0, y: self.rng.gen_range(0.0 .. Self::SIDE_SPAWN_MAX_Y) },
+            SpawnEdge::Right => Point2 { x: max_x, y: self.rng.gen_range(0.0 .. Self::SIDE_SPAWN_MAX_Y) },
+        };
+        let label = String::from(self.words.random(&mut self.rng));
+        let kind = if self.rng.gen_bool(Self::TANK_CHANCE) {
+            EnemyKind::Tank
+        } else if self.rng.gen_bool(Self::FAST_CHANCE) {
+            EnemyKind::Fast
+        } else if self.rng.gen_bool(Self::SPLITTER_CHANCE) {
+            EnemyKind::Splitter
+        } else {
+            EnemyKind::Grunt
+        };
+        let speed = kind.stats().speed * self.rng.gen_range(0.5 .. 1.5) * difficulty.enemy_speed_multiplier();
+        let fire_interval = if self.rng.gen_bool(Self::SHOOTER_CHANCE) {
+            Some(Self::SHOOTER_FIRE_INTERVAL)
+        } else {
+            None
+        };
+
+        let seeks_player = self.rng.gen_bool(Self::SEEKER_CHANCE);
+
+        Spawn { kind, label, pos, speed, fire_interval, seeks_player, edge }
+    }
+}
@@ -0,0 +1,70 @@
+use ggez::graphics;
+use ggez::mint::Point2;
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Star {
+    pub pos: Point2<f32>,
+    pub speed: f32,
+    // 1 is the farthest layer, `Starfield::LAYERS` the nearest
+    pub depth: u32,
+}
+
+impl Star {
+    pub fn size(&self) -> f32 {
+        self.depth as f32
+    }
+
+    pub fn brightness(&self) -> f32 {
+        self.depth as f32 / Starfield::LAYERS as f32
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Starfield {
+    pub stars: Vec<Star>,
+    screen_height: f32,
+}
+
+impl Starfield {
+    pub const LAYERS: u32 = 3;
+    pub const BASE_SPEED: f32 = 40.0;
+
+    pub fn new<R: Rng>(count: usize, screen_width: f32, screen_height: f32, rng: &mut R) -> Self {
+        // Nearer stars scroll faster, which gives the parallax effect
+        let stars = (0..count).map(|_| {
+            let depth = rng.gen_range(1..=Self::LAYERS);
+            Star {
+                pos: Point2 {
+                    x: rng.gen_range(0.0..screen_width),
+                    y: rng.gen_range(0.0..screen_height),
+                },
+                speed: Self::BASE_SPEED * depth as f32,
+                depth,
+            }
+        }).collect();
+
+        Starfield { stars, screen_height }
+    }
+
+    pub fn update(&mut self, seconds: f32) {
+        for star in &mut self.stars {
+            star.pos.y += star.speed * seconds;
+
+            // Keep however far past the bottom it went, so the spacing between stars stays the same
+            if star.pos.y >= self.screen_height {
+                star.pos.y -= self.screen_height;
+            }
+        }
+    }
+
+    pub fn draw(&self, canvas: &mut graphics::Canvas) {
+        for star in &self.stars {
+            let brightness = star.brightness();
+            let draw_params = graphics::DrawParam::default().
+                dest_rect(graphics::Rect::new(star.pos.x, star.pos.y, star.size(), star.size())).
+                color(graphics::Color::new(brightness, brightness, brightness, 1.0));
+            canvas.draw(&graphics::Quad, draw_params);
+        }
+    }
+}
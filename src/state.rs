@@ -0,0 +1,26 @@
+use crate::input::Action;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameState {
+    Menu,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+impl GameState {
+    pub fn handle_action(self, action: Action) -> GameState {
+        match (self, action) {
+            (GameState::Menu, Action::Confirm)     => GameState::Playing,
+            (GameState::Playing, Action::Pause)    => GameState::Paused,
+            (GameState::Paused, Action::Pause)     => GameState::Playing,
+            (GameState::GameOver, Action::Confirm) => GameState::Menu,
+            (state, _) => state,
+        }
+    }
+
+    // Entities only move while the game is actually being played
+    pub fn is_simulating(self) -> bool {
+        self == GameState::Playing
+    }
+}
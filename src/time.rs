@@ -0,0 +1,118 @@
+// Gameplay updates use scaled time so the whole game can be slowed down or sped up. Anything that
+// should keep running at normal speed, like UI timers, keeps using the raw delta.
+pub fn scale_delta(raw_seconds: f32, time_scale: f32) -> f32 {
+    raw_seconds * time_scale.max(0.0)
+}
+
+// Most game time the update loop catches up on in one frame. Anything longer, like after dragging
+// the window, is dropped instead of played out in a burst.
+pub const MAX_DELTA: f32 = 1.0 / 20.0;
+
+pub fn clamp_delta(raw_seconds: f32) -> f32 {
+    raw_seconds.clamp(0.0, MAX_DELTA)
+}
+
+// How many fixed steps of `tick_seconds` to run when the game is `behind_seconds` behind. Past
+// `MAX_DELTA` the rest is dropped.
+pub fn catch_up_ticks(behind_seconds: f32, tick_seconds: f32) -> u32 {
+    (clamp_delta(behind_seconds) / tick_seconds).round() as u32
+}
+
+// A short freeze of the whole game to sell a big hit. It counts down in real time, since gameplay
+// time isn't moving while it's going.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HitStop {
+    time_left: f32,
+}
+
+impl HitStop {
+    pub fn new() -> Self {
+        HitStop { time_left: 0.0 }
+    }
+
+    // A shorter freeze doesn't cut a longer one short
+    pub fn trigger(&mut self, duration: f32) {
+        self.time_left = f32::max(self.time_left, duration);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.time_left > 0.0
+    }
+
+    // Returns whether gameplay should skip this update. Takes the raw delta so the time scale
+    // doesn't stretch the freeze out.
+    pub fn update(&mut self, raw_seconds: f32) -> bool {
+        if !self.is_active() {
+            return false;
+        }
+
+        self.time_left = f32::max(self.time_left - raw_seconds, 0.0);
+        true
+    }
+}
+
+// The one place gameplay time comes from. Real time always moves on, but game time only moves
+// while the game isn't paused, and at the time scale, so timers updated from `delta` can't drift
+// apart from each other across a pause.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameClock {
+    delta: f32,
+    elapsed: f32,
+    real_delta: f32,
+    real_elapsed: f32,
+    paused: bool,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        GameClock::new()
+    }
+}
+
+impl GameClock {
+    pub fn new() -> Self {
+        GameClock { delta: 0.0, elapsed: 0.0, real_delta: 0.0, real_elapsed: 0.0, paused: false }
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    // Takes the fixed step of the update loop, which caps how many steps it catches up on after a
+    // stall, so there's no long delta to clamp here
+    pub fn tick(&mut self, raw_seconds: f32, time_scale: f32) {
+        self.real_delta = raw_seconds;
+        self.real_elapsed += self.real_delta;
+
+        self.delta = if self.paused { 0.0 } else { scale_delta(self.real_delta, time_scale) };
+        self.elapsed += self.delta;
+    }
+
+    // Gameplay seconds since the last tick
+    pub fn delta(&self) -> f32 {
+        self.delta
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    // For UI, which keeps going while the game is paused
+    pub fn real_delta(&self) -> f32 {
+        self.real_delta
+    }
+
+    pub fn real_elapsed(&self) -> f32 {
+        self.real_elapsed
+    }
+
+    // Starts game time over for a new game, real time keeps counting
+    pub fn reset(&mut self) {
+        self.delta = 0.0;
+        self.elapsed = 0.0;
+    }
+}
@@ -0,0 +1,99 @@
+use ggez::mint::Vector2;
+
+use crate::movement::{MovementPattern, SeekPlayer, SineWave, StraightDown, Zigzag};
+
+// Movement patterns an enemy can switch to partway through its timeline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PatternKind {
+    StraightDown,
+    SineWave { amplitude: f32, period: f32 },
+    Zigzag { amplitude: f32, period: f32 },
+    SeekPlayer,
+}
+
+impl PatternKind {
+    pub fn build(self) -> Box<dyn MovementPattern> {
+        match self {
+            PatternKind::StraightDown => Box::new(StraightDown),
+            PatternKind::SineWave { amplitude, period } => Box::new(SineWave { amplitude, period }),
+            PatternKind::Zigzag { amplitude, period } => Box::new(Zigzag { amplitude, period }),
+            PatternKind::SeekPlayer => Box::new(SeekPlayer::default()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    // A fan of `count` shots aimed down, `spread` radians apart
+    FireVolley { count: usize, spread: f32 },
+    ChangePattern(PatternKind),
+    // Uses the knockback impulse, so the dash fades out on its own
+    Dash(Vector2<f32>),
+}
+
+// Actions scheduled at times since the timeline started. A looping timeline starts over from the
+// first action every `loop_length` seconds.
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    events: Vec<(f32, Action)>,
+    elapsed: f32,
+    next: usize,
+    loop_length: Option<f32>,
+}
+
+impl Timeline {
+    pub fn new(mut events: Vec<(f32, Action)>) -> Self {
+        events.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Timeline { events, elapsed: 0.0, next: 0, loop_length: None }
+    }
+
+    // The loop is never shorter than the time of the last action, so every action gets its turn.
+    // Non-positive lengths are ignored, since they would loop forever.
+    pub fn with_loop(mut self, loop_length: f32) -> Self {
+        let last = self.events.last().map_or(0.0, |event| event.0);
+        let loop_length = f32::max(loop_length, last);
+        self.loop_length = if loop_length > 0.0 { Some(loop_length) } else { None };
+        self
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.loop_length.is_some()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.loop_length.is_none() && self.next == self.events.len()
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.next = 0;
+    }
+
+    // Returns the actions that came due during this update, in order. Long updates can go
+    // through more than one pass of a looping timeline.
+    pub fn advance(&mut self, seconds: f32) -> Vec<Action> {
+        let mut due = Vec::new();
+        self.elapsed += seconds;
+
+        loop {
+            while self.next < self.events.len() && self.events[self.next].0 <= self.elapsed {
+                due.push(self.events[self.next].1);
+                self.next += 1;
+            }
+
+            match self.loop_length {
+                Some(loop_length) if self.next == self.events.len() && self.elapsed >= loop_length => {
+                    self.elapsed -= loop_length;
+                    self.next = 0;
+                },
+                _ => break,
+            }
+        }
+
+        due
+    }
+}
@@ -0,0 +1,75 @@
+use ggez::Context;
+
+use crate::assets::read_text_file;
+use crate::entities::{Player, Shot};
+
+// Balance numbers that can be changed from a file without recompiling. The defaults are the
+// game's usual values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tuning {
+    pub player_speed: f32,
+    pub shot_timeout: f32,
+    pub shot_speed: f32,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Tuning {
+            player_speed: Player::SPEED,
+            shot_timeout: Player::SHOT_TIMEOUT,
+            shot_speed: Shot::SPEED,
+        }
+    }
+}
+
+impl Tuning {
+    const PATH: &'static str = "/tuning.conf";
+
+    pub fn load(ctx: &Context) -> Tuning {
+        Tuning::from_file(read_text_file(ctx, Self::PATH).as_deref())
+    }
+
+    // Falls back to the defaults if there's no tuning file or it can't be parsed
+    pub fn from_file(contents: Option<&str>) -> Tuning {
+        let contents = match contents {
+            Some(contents) => contents,
+            None => return Tuning::default(),
+        };
+
+        Tuning::parse(contents).unwrap_or_else(|e| {
+            eprintln!("Warning: ignoring {}: {}", Self::PATH, e);
+            Tuning::default()
+        })
+    }
+
+    // Parses lines like `player_speed = 400`, with `#` starting a comment. Anything that isn't
+    // mentioned keeps its default.
+    pub fn parse(contents: &str) -> Result<Tuning, String> {
+        let mut tuning = Tuning::default();
+
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (name, value) = line.split_once('=').
+                ok_or_else(|| format!("line {}: expected `name = value`", number + 1))?;
+            let (name, value) = (name.trim(), value.trim());
+            let value: f32 = value.parse().
+                map_err(|_| format!("line {}: `{}` isn't a number", number + 1, value))?;
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!("line {}: `{}` can't be negative", number + 1, name));
+            }
+
+            match name {
+                "player_speed" => tuning.player_speed = value,
+                "shot_timeout" => tuning.shot_timeout = value,
+                "shot_speed" => tuning.shot_speed = value,
+                _ => return Err(format!("line {}: unknown setting `{}`", number + 1, name)),
+            }
+        }
+
+        Ok(tuning)
+    }
+}
@@ -0,0 +1,82 @@
+use crate::entities::Enemy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keystroke {
+    // Didn't continue any label, or wasn't a typeable character
+    Miss,
+    // Continued the target's label, which is now locked on to
+    Progress { target: u64 },
+    // Finished the target's label
+    Completed { target: u64 },
+}
+
+// Type-to-shoot controls: typing the start of an enemy's label locks on to it, and finishing the
+// label destroys it. Matching ignores case, and the line breaks in long labels are typed as spaces.
+#[derive(Debug, Clone, Default)]
+pub struct TypingState {
+    buffer: String,
+    target: Option<u64>,
+}
+
+impl TypingState {
+    pub fn new() -> Self {
+        TypingState::default()
+    }
+
+    pub fn buffer(&self) -> &str {
+        &self.buffer
+    }
+
+    // The id of the enemy being typed, if any
+    pub fn target(&self) -> Option<u64> {
+        self.target
+    }
+
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.target = None;
+    }
+
+    pub fn type_char(&mut self, character: char, enemies: &[Enemy]) -> Keystroke {
+        if character.is_control() {
+            return Keystroke::Miss;
+        }
+
+        // Start over if whatever we were typing got destroyed some other way
+        if let Some(target) = self.target {
+            if !enemies.iter().any(|enemy| enemy.is_alive && enemy.id() == target) {
+                self.clear();
+            }
+        }
+
+        let mut typed = self.buffer.clone();
+        typed.extend(character.to_lowercase());
+
+        // Once locked on, other enemies with the same prefix are ignored until this one is done.
+        // Otherwise go for whichever matching enemy is furthest down the screen.
+        let matching = enemies.iter().
+            filter(|enemy| enemy.is_alive).
+            filter(|enemy| self.target.is_none_or(|target| enemy.id() == target)).
+            filter(|enemy| normalize(enemy.label()).starts_with(&typed)).
+            max_by(|a, b| a.pos.y.total_cmp(&b.pos.y));
+
+        let enemy = match matching {
+            Some(enemy) => enemy,
+            None => return Keystroke::Miss,
+        };
+
+        let target = enemy.id();
+        if normalize(enemy.label()) == typed {
+            self.clear();
+            Keystroke::Completed { target }
+        } else {
+            self.buffer = typed;
+            self.target = Some(target);
+            Keystroke::Progress { target }
+        }
+    }
+}
+
+fn normalize(label: &str) -> String {
+    label.replace('\n', " ").to_lowercase()
+}
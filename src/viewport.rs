@@ -0,0 +1,81 @@
+use ggez::graphics;
+use ggez::mint::Point2;
+
+use crate::screen::ScreenConfig;
+
+// Maps the fixed virtual resolution the game plays at onto the window, scaled as large as it fits
+// without stretching. Whatever's left over on the sides or top and bottom gets black bars.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub virtual_size: ScreenConfig,
+    pub window: ScreenConfig,
+}
+
+impl Viewport {
+    pub const BAR_COLOR: graphics::Color = graphics::Color::BLACK;
+
+    pub fn new(virtual_size: ScreenConfig, window: ScreenConfig) -> Self {
+        Viewport { virtual_size, window }
+    }
+
+    pub fn resize(&mut self, width: f32, height: f32) {
+        self.window = ScreenConfig::new(width, height);
+    }
+
+    // Window pixels per virtual unit
+    pub fn scale(&self) -> f32 {
+        f32::min(self.window.width / self.virtual_size.width, self.window.height / self.virtual_size.height)
+    }
+
+    // Where the top left of the virtual screen is in the window
+    pub fn offset(&self) -> Point2<f32> {
+        let scale = self.scale();
+        Point2 {
+            x: (self.window.width - self.virtual_size.width * scale) / 2.0,
+            y: (self.window.height - self.virtual_size.height * scale) / 2.0,
+        }
+    }
+
+    pub fn to_virtual(&self, window_point: Point2<f32>) -> Point2<f32> {
+        let scale = self.scale();
+        let offset = self.offset();
+        Point2 {
+            x: (window_point.x - offset.x) / scale,
+            y: (window_point.y - offset.y) / scale,
+        }
+    }
+
+    // Screen coordinates for the canvas that show `view`, a rect in virtual space, letterboxed in
+    // the window. The rect is grown to cover the bars too.
+    pub fn letterbox(&self, view: graphics::Rect) -> graphics::Rect {
+        let scale = self.scale();
+        let offset = self.offset();
+        graphics::Rect::new(
+            view.x - offset.x / scale,
+            view.y - offset.y / scale,
+            self.window.width / scale,
+            self.window.height / scale,
+        )
+    }
+
+    // The bars around the virtual screen, in virtual space. Empty bars are left out.
+    pub fn bars(&self) -> Vec<graphics::Rect> {
+        let full = self.letterbox(graphics::Rect::new(0.0, 0.0, self.virtual_size.width, self.virtual_size.height));
+        let (width, height) = (self.virtual_size.width, self.virtual_size.height);
+
+        [
+            graphics::Rect::new(full.x, full.y, -full.x, full.h),
+            graphics::Rect::new(width, full.y, full.right() - width, full.h),
+            graphics::Rect::new(full.x, full.y, full.w, -full.y),
+            graphics::Rect::new(full.x, height, full.w, full.bottom() - height),
+        ].into_iter().
+            filter(|bar| bar.w > 0.0 && bar.h > 0.0).
+            collect()
+    }
+
+    pub fn draw_bars(&self, canvas: &mut graphics::Canvas) {
+        for bar in self.bars() {
+            canvas.draw(&graphics::Quad, graphics::DrawParam::default().dest_rect(bar).color(Self::BAR_COLOR));
+        }
+    }
+}
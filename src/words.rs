@@ -0,0 +1,60 @@
+use ggez::Context;
+use rand::Rng;
+
+use crate::assets::read_text_file;
+use crate::spawner::Spawner;
+
+// Labels for spawned enemies. A words file has one label per line, with any line break written as
+// `\n`. Blank lines and anything after a `#` are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordList {
+    words: Vec<String>,
+}
+
+impl Default for WordList {
+    fn default() -> Self {
+        WordList::new(Spawner::ENEMIES.iter().map(|word| String::from(*word)).collect())
+    }
+}
+
+impl WordList {
+    pub const PATH: &'static str = "/words.txt";
+    // Used when the list is empty, so there's always something to show
+    pub const FALLBACK_WORD: &'static str = "Segfaults";
+
+    pub fn new(words: Vec<String>) -> Self {
+        WordList { words }
+    }
+
+    // Falls back to the built-in words if there's no words file
+    pub fn load(ctx: &Context) -> WordList {
+        let contents = match read_text_file(ctx, Self::PATH) {
+            Some(contents) => contents,
+            None => return WordList::default(),
+        };
+
+        WordList::parse(&contents)
+    }
+
+    pub fn parse(contents: &str) -> WordList {
+        let words = contents.lines().
+            map(|line| line.split('#').next().unwrap().trim()).
+            filter(|line| !line.is_empty()).
+            map(|line| line.replace("\\n", "\n")).
+            collect();
+
+        WordList::new(words)
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    pub fn random<R: Rng>(&self, rng: &mut R) -> &str {
+        if self.words.is_empty() {
+            return Self::FALLBACK_WORD;
+        }
+
+        &self.words[rng.gen_range(0 .. self.words.len())]
+    }
+}
@@ -0,0 +1,24 @@
+use ggez::graphics;
+use ggez::mint::Point2;
+
+use shooter::assets::Sprite;
+
+// Stands in for a real sprite, which would need a graphics context
+#[derive(Debug, Clone, Copy)]
+pub struct MockSprite {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for MockSprite {
+    fn default() -> Self {
+        MockSprite { width: 100.0, height: 50.0 }
+    }
+}
+
+impl Sprite for MockSprite {
+    fn draw(&mut self, _center: Point2<f32>, _canvas: &mut graphics::Canvas) {}
+
+    fn width(&self) -> f32 { self.width }
+    fn height(&self) -> f32 { self.height }
+}
@@ -0,0 +1,133 @@
+use ggez::graphics::Rect;
+use ggez::mint::Point2;
+
+use shooter::assets::{or_placeholder, text_center, text_origin, AnimatedSprite, Animation, Assets, AudioSettings, Skin, Sprite, TextSprite};
+
+#[test]
+fn animation_advances_one_frame_per_frame_duration() {
+    let mut animation = Animation::new(3, 10.0).unwrap();
+    assert_eq!(animation.current_frame(), 0);
+
+    animation.update(0.05);
+    assert_eq!(animation.current_frame(), 0);
+
+    animation.update(0.05);
+    assert_eq!(animation.current_frame(), 1);
+}
+
+#[test]
+fn animation_wraps_around_at_the_end() {
+    let mut animation = Animation::new(3, 4.0).unwrap();
+
+    animation.update(0.25);
+    animation.update(0.25);
+    assert_eq!(animation.current_frame(), 2);
+
+    animation.update(0.25);
+    assert_eq!(animation.current_frame(), 0);
+}
+
+#[test]
+fn animation_needs_a_positive_frame_rate() {
+    assert!(Animation::new(3, 0.0).is_err());
+    assert!(Animation::new(3, -4.0).is_err());
+    assert!(Animation::new(3, f32::NAN).is_err());
+}
+
+#[test]
+fn animated_sprite_needs_frames() {
+    assert!(AnimatedSprite::new(Vec::new(), 10.0).is_err());
+}
+
+#[test]
+fn failed_load_falls_back_to_placeholder() {
+    let missing: Result<&str, String> = Err(String::from("not found"));
+    assert_eq!(or_placeholder(missing, "/missing.png", || "placeholder"), "placeholder");
+}
+
+#[test]
+fn successful_load_keeps_real_asset() {
+    let loaded: Result<&str, String> = Ok("image");
+    assert_eq!(or_placeholder(loaded, "/shot.png", || "placeholder"), "image");
+}
+
+#[test]
+fn muted_audio_plays_nothing() {
+    let settings = AudioSettings { master_volume: 1.0, muted: true };
+    let mut played = None;
+
+    settings.play(Assets::SHOT_VOLUME, |volume| played = Some(volume));
+    assert_eq!(played, None);
+}
+
+#[test]
+fn master_volume_scales_sound_volume() {
+    let mut settings = AudioSettings { master_volume: 0.5, muted: false };
+    let mut played = None;
+
+    settings.play(Assets::SHOT_VOLUME, |volume| played = Some(volume));
+    assert_eq!(played, Some(Assets::SHOT_VOLUME * 0.5));
+
+    settings.toggle_mute();
+    settings.toggle_mute();
+    played = None;
+    settings.play(Assets::SHOT_VOLUME, |volume| played = Some(volume));
+    assert!(played.is_some());
+}
+
+#[test]
+fn muted_volume_is_silent() {
+    let mut settings = AudioSettings { master_volume: 0.8, muted: false };
+    assert_eq!(settings.volume(Assets::MUSIC_VOLUME), Assets::MUSIC_VOLUME * 0.8);
+
+    settings.toggle_mute();
+    assert_eq!(settings.volume(Assets::MUSIC_VOLUME), 0.0);
+}
+
+#[test]
+fn rotated_text_turns_around_its_hitbox_center() {
+    // Odd and even widths, as a label's hitbox would be, at a position between pixels
+    for (width, height) in [(37.0, 21.0), (40.0, 20.0)] {
+        let hitbox = Rect::new(100.25, 49.5, width, height);
+        let center = text_center(Point2 { x: hitbox.x, y: hitbox.y }, width, height);
+
+        assert_eq!(center.x, hitbox.x + hitbox.w / 2.0);
+        assert_eq!(center.y, hitbox.y + hitbox.h / 2.0);
+    }
+}
+
+#[test]
+fn text_position_snaps_to_whole_pixels() {
+    for (x, y) in [(100.3, 49.6), (100.7, 50.25), (99.5, 50.0)] {
+        let origin = text_origin(Point2 { x, y });
+
+        assert_eq!(origin.x.fract(), 0.0);
+        assert_eq!(origin.y.fract(), 0.0);
+        assert!((origin.x - x).abs() <= 0.5 && (origin.y - y).abs() <= 0.5);
+    }
+
+    let origin = text_origin(Point2 { x: 100.3, y: 49.6 });
+    assert_eq!((origin.x, origin.y), (100.0, 50.0));
+}
+
+#[test]
+fn skins_cycle_both_ways() {
+    assert_eq!(Skin::Ferris.next(), Skin::Golden);
+    assert_eq!(Skin::ALL[Skin::ALL.len() - 1].next(), Skin::Ferris);
+    assert_eq!(Skin::Ferris.previous(), Skin::ALL[Skin::ALL.len() - 1]);
+
+    for skin in Skin::ALL {
+        assert_eq!(skin.next().previous(), skin);
+    }
+}
+
+#[test]
+fn text_shadow_doesnt_change_hitbox() {
+    let plain = TextSprite::from_text(ggez::graphics::Text::new("Bloat"), Rect::new(0.0, 0.0, 75.0, 38.0));
+    let shadowed = plain.clone().with_shadow();
+
+    assert!(!plain.has_shadow());
+    assert!(shadowed.has_shadow());
+    assert_eq!(shadowed.width(), plain.width());
+    assert_eq!(shadowed.height(), plain.height());
+}
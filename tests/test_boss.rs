@@ -0,0 +1,54 @@
+use shooter::boss::*;
+use shooter::entities::EnemyKind;
+
+mod common;
+use common::MockSprite;
+
+fn boss() -> Boss {
+    Boss::with_sprite(1200.0, Box::new(MockSprite { width: 200.0, height: 50.0 })).unwrap()
+}
+
+#[test]
+fn phases_advance_once_per_threshold() {
+    let mut boss = boss();
+    let max_health = EnemyKind::Boss.stats().health;
+    assert_eq!(boss.phase, BossPhase::One);
+
+    let mut phases = Vec::new();
+    for _ in 0..max_health - 1 {
+        let before = boss.phase;
+        assert!(!boss.take_damage(1));
+        if boss.phase != before {
+            phases.push(boss.phase);
+        }
+    }
+
+    assert_eq!(phases, vec![BossPhase::Two, BossPhase::Three]);
+    assert!(boss.take_damage(1));
+    assert!(!boss.is_alive());
+}
+
+#[test]
+fn big_hit_can_skip_a_phase() {
+    let mut boss = boss();
+    let max_health = EnemyKind::Boss.stats().health;
+
+    boss.take_damage(max_health * 3 / 4);
+    assert_eq!(boss.phase, BossPhase::Three);
+}
+
+#[test]
+fn boss_enters_then_sweeps_and_fires() {
+    let mut boss = boss();
+
+    let mut shots = Vec::new();
+    for _ in 0..60 * 5 {
+        shots.extend(boss.update(1.0 / 60.0, 1200.0));
+        assert!(boss.enemy.pos.x >= 0.0 && boss.enemy.pos.x <= 1000.0);
+    }
+
+    assert_eq!(boss.enemy.pos.y, Boss::HOVER_Y);
+    assert!(!shots.is_empty());
+    assert_eq!(shots.len() % BossPhase::One.volley_size(), 0);
+    assert!(shots.iter().all(|shot| shot.velocity().y > 0.0));
+}
@@ -0,0 +1,108 @@
+use ggez::mint::{Point2, Vector2};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use shooter::camera::*;
+use shooter::screen::ScreenConfig;
+
+fn magnitude(x: f32, y: f32) -> f32 {
+    (x * x + y * y).sqrt()
+}
+
+#[test]
+fn shake_offset_returns_to_zero() {
+    let mut shake = ScreenShake::new(StdRng::seed_from_u64(7));
+    shake.trigger(10.0);
+
+    let first = shake.update(1.0 / 60.0);
+    assert!(magnitude(first.x, first.y) > 0.0);
+    assert!(magnitude(first.x, first.y) <= 10.0);
+
+    let mut elapsed = 1.0 / 60.0;
+    while elapsed < ScreenShake::DECAY_TIME {
+        shake.update(1.0 / 60.0);
+        elapsed += 1.0 / 60.0;
+    }
+
+    let offset = shake.update(1.0 / 60.0);
+    assert!(!shake.is_shaking());
+    assert_eq!((offset.x, offset.y), (0.0, 0.0));
+}
+
+#[test]
+fn shake_decays_and_is_capped() {
+    let mut shake = ScreenShake::new(StdRng::seed_from_u64(7));
+    shake.trigger(1000.0);
+
+    let early = shake.update(0.01);
+    let early = magnitude(early.x, early.y);
+    assert!(early <= ScreenShake::MAX_INTENSITY);
+
+    let late = shake.update(ScreenShake::DECAY_TIME * 0.8);
+    assert!(magnitude(late.x, late.y) < early);
+}
+
+fn tall_camera() -> Camera {
+    Camera::new(ScreenConfig::new(800.0, 600.0), ScreenConfig::new(800.0, 1800.0))
+}
+
+#[test]
+fn camera_moves_toward_target_smoothly() {
+    let mut camera = tall_camera();
+    let target = Point2 { x: 400.0, y: 1000.0 };
+
+    camera.follow(target, 0.1);
+    // Centering on the target would put the top of the view at 700
+    assert!(camera.pos.y > 0.0);
+    assert!(camera.pos.y < 700.0);
+
+    let first_step = camera.pos.y;
+    camera.follow(target, 0.1);
+    assert!(camera.pos.y > first_step);
+
+    for _ in 0..100 {
+        camera.follow(target, 0.1);
+    }
+    assert!((camera.pos.y - 700.0).abs() < 0.01);
+}
+
+#[test]
+fn camera_stays_inside_world() {
+    let mut camera = tall_camera();
+
+    camera.center_on(Point2 { x: 400.0, y: 1790.0 });
+    assert_eq!(camera.pos.y, 1200.0);
+
+    for _ in 0..100 {
+        camera.follow(Point2 { x: 400.0, y: -500.0 }, 0.1);
+        assert!(camera.pos.y >= 0.0);
+    }
+    assert!(camera.view_rect(Vector2 { x: 0.0, y: 0.0 }).top().abs() < 0.01);
+}
+
+#[test]
+fn camera_converts_between_screen_and_world() {
+    let mut camera = tall_camera();
+    camera.center_on(Point2 { x: 400.0, y: 1000.0 });
+
+    let world = camera.to_world(Point2 { x: 10.0, y: 20.0 });
+    assert_eq!((world.x, world.y), (10.0, 720.0));
+
+    let screen = camera.to_screen(world);
+    assert_eq!((screen.x, screen.y), (10.0, 20.0));
+}
+
+#[test]
+fn damage_flash_fades_to_nothing_over_its_duration() {
+    let mut flash = DamageFlash::new();
+    assert_eq!(flash.alpha(), 0.0);
+
+    flash.trigger();
+    assert_eq!(flash.alpha(), DamageFlash::MAX_ALPHA);
+
+    flash.update(DamageFlash::DURATION / 2.0);
+    assert!((flash.alpha() - DamageFlash::MAX_ALPHA / 2.0).abs() < 0.001);
+
+    flash.update(DamageFlash::DURATION / 2.0);
+    assert_eq!(flash.alpha(), 0.0);
+}
@@ -0,0 +1,174 @@
+use ggez::graphics::Rect;
+use ggez::mint::Point2;
+
+use shooter::collision::*;
+
+#[test]
+fn overlapping_rects_collide() {
+    let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+    let b = Rect::new(5.0, 5.0, 10.0, 10.0);
+
+    assert!(rect_overlap(a, b));
+    assert!(rect_overlap(b, a));
+}
+
+#[test]
+fn contained_rects_collide() {
+    let outer = Rect::new(0.0, 0.0, 100.0, 100.0);
+    let inner = Rect::new(40.0, 40.0, 10.0, 10.0);
+
+    assert!(rect_overlap(outer, inner));
+    assert!(rect_overlap(inner, outer));
+}
+
+#[test]
+fn edge_touching_rects_dont_collide() {
+    let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+
+    assert!(!rect_overlap(a, Rect::new(10.0, 0.0, 10.0, 10.0)));
+    assert!(!rect_overlap(a, Rect::new(0.0, 10.0, 10.0, 10.0)));
+    assert!(!rect_overlap(a, Rect::new(-10.0, 0.0, 10.0, 10.0)));
+    assert!(!rect_overlap(a, Rect::new(10.0, 10.0, 10.0, 10.0)));
+}
+
+#[test]
+fn disjoint_rects_dont_collide() {
+    let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+
+    assert!(!rect_overlap(a, Rect::new(20.0, 0.0, 10.0, 10.0)));
+    assert!(!rect_overlap(a, Rect::new(0.0, -20.0, 10.0, 10.0)));
+    assert!(!rect_overlap(a, Rect::new(5.0, 20.0, 1.0, 1.0)));
+}
+
+#[test]
+fn point_in_rect_includes_top_left_edges_only() {
+    let r = Rect::new(0.0, 0.0, 10.0, 10.0);
+
+    assert!(point_in_rect(Point2 { x: 5.0, y: 5.0 }, r));
+    assert!(point_in_rect(Point2 { x: 0.0, y: 0.0 }, r));
+    assert!(!point_in_rect(Point2 { x: 10.0, y: 5.0 }, r));
+    assert!(!point_in_rect(Point2 { x: 5.0, y: 10.0 }, r));
+    assert!(!point_in_rect(Point2 { x: -1.0, y: 5.0 }, r));
+}
+
+struct Square(Rect);
+
+impl Collidable for Square {
+    fn bounding_rect(&self) -> Rect { self.0 }
+}
+
+#[test]
+fn collidables_collide_by_bounding_rect() {
+    let a = Square(Rect::new(0.0, 0.0, 10.0, 10.0));
+
+    assert!(collides(&a, &Square(Rect::new(9.0, 9.0, 10.0, 10.0))));
+    assert!(!collides(&a, &Square(Rect::new(10.0, 0.0, 10.0, 10.0))));
+}
+
+#[test]
+fn grid_query_returns_exactly_overlapping_rects() {
+    let mut grid = SpatialGrid::new(50.0);
+    grid.insert(0, Rect::new(0.0, 0.0, 10.0, 10.0));
+    grid.insert(1, Rect::new(40.0, 40.0, 30.0, 30.0));
+    grid.insert(2, Rect::new(200.0, 200.0, 10.0, 10.0));
+    grid.insert(3, Rect::new(-30.0, -30.0, 25.0, 25.0));
+
+    assert_eq!(grid.query(Rect::new(5.0, 5.0, 40.0, 40.0)), vec![0, 1]);
+    assert_eq!(grid.query(Rect::new(60.0, 60.0, 5.0, 5.0)), vec![1]);
+    assert_eq!(grid.query(Rect::new(-10.0, -10.0, 5.0, 5.0)), vec![3]);
+    assert!(grid.query(Rect::new(100.0, 100.0, 50.0, 50.0)).is_empty());
+}
+
+#[test]
+fn grid_does_far_fewer_comparisons_than_brute_force() {
+    let mut grid = SpatialGrid::new(100.0);
+
+    let enemies: Vec<Rect> = (0..500).map(|i| {
+        Rect::new((i % 25) as f32 * 48.0, (i / 25) as f32 * 50.0, 40.0, 30.0)
+    }).collect();
+    let shots: Vec<Rect> = (0..200).map(|i| {
+        Rect::new((i % 20) as f32 * 60.0 + 3.0, (i / 20) as f32 * 100.0 + 7.0, 16.0, 16.0)
+    }).collect();
+
+    for (id, rect) in enemies.iter().enumerate() {
+        grid.insert(id, *rect);
+    }
+
+    for shot in shots.iter() {
+        let brute_force: Vec<usize> = enemies.iter().enumerate().
+            filter(|(_, enemy)| rect_overlap(*shot, **enemy)).
+            map(|(id, _)| id).
+            collect();
+        assert_eq!(grid.query(*shot), brute_force);
+    }
+
+    let brute_force_comparisons = enemies.len() * shots.len();
+    assert!(grid.comparisons() * 20 < brute_force_comparisons);
+}
+
+#[test]
+fn segment_through_rect_intersects() {
+    let r = Rect::new(0.0, 0.0, 10.0, 10.0);
+
+    assert!(segment_intersects_rect(Point2 { x: 5.0, y: 50.0 }, Point2 { x: 5.0, y: -50.0 }, r));
+    assert!(segment_intersects_rect(Point2 { x: -5.0, y: -5.0 }, Point2 { x: 15.0, y: 15.0 }, r));
+}
+
+#[test]
+fn segment_missing_or_short_of_rect_doesnt_intersect() {
+    let r = Rect::new(0.0, 0.0, 10.0, 10.0);
+
+    assert!(!segment_intersects_rect(Point2 { x: 20.0, y: 50.0 }, Point2 { x: 20.0, y: -50.0 }, r));
+    assert!(!segment_intersects_rect(Point2 { x: 5.0, y: 50.0 }, Point2 { x: 5.0, y: 20.0 }, r));
+    assert!(!segment_intersects_rect(Point2 { x: 10.0, y: 50.0 }, Point2 { x: 10.0, y: -50.0 }, r));
+}
+
+#[test]
+fn quadtree_query_returns_exactly_overlapping_rects() {
+    let mut tree = QuadTree::new(Rect::new(0.0, 0.0, 1000.0, 1000.0));
+
+    // Enough clustered in one corner to split it a few levels down
+    let mut rects: Vec<Rect> = (0..60).map(|i| {
+        Rect::new((i % 8) as f32 * 15.0, (i / 8) as f32 * 15.0, 10.0, 10.0)
+    }).collect();
+    // Straddling the first and second splits, and hanging off the edge
+    rects.push(Rect::new(490.0, 490.0, 20.0, 20.0));
+    rects.push(Rect::new(240.0, 100.0, 20.0, 20.0));
+    rects.push(Rect::new(980.0, -10.0, 40.0, 40.0));
+
+    for (id, rect) in rects.iter().enumerate() {
+        tree.insert(id, *rect);
+    }
+
+    let queries = [
+        Rect::new(0.0, 0.0, 1000.0, 1000.0),
+        Rect::new(5.0, 5.0, 30.0, 30.0),
+        Rect::new(495.0, 480.0, 2.0, 60.0),
+        Rect::new(250.0, 110.0, 1.0, 1.0),
+        Rect::new(990.0, -20.0, 5.0, 5.0),
+        Rect::new(600.0, 600.0, 100.0, 100.0),
+    ];
+    for query in queries {
+        let brute_force: Vec<usize> = rects.iter().enumerate().
+            filter(|(_, rect)| rect_overlap(query, **rect)).
+            map(|(id, _)| id).
+            collect();
+        assert_eq!(tree.query(query), brute_force);
+    }
+
+    assert_eq!(tree.query(Rect::new(495.0, 480.0, 2.0, 60.0)), vec![60]);
+    assert!(tree.query(Rect::new(600.0, 600.0, 100.0, 100.0)).is_empty());
+}
+
+#[test]
+fn quadtree_depth_is_capped_for_stacked_rects() {
+    let mut tree = QuadTree::new(Rect::new(0.0, 0.0, 1000.0, 1000.0));
+    for id in 0..200 {
+        tree.insert(id, Rect::new(1.0, 1.0, 1.0, 1.0));
+    }
+
+    assert_eq!(tree.query(Rect::new(0.0, 0.0, 5.0, 5.0)), (0..200).collect::<Vec<_>>());
+
+    tree.clear();
+    assert!(tree.query(Rect::new(0.0, 0.0, 1000.0, 1000.0)).is_empty());
+}
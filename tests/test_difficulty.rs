@@ -0,0 +1,86 @@
+use shooter::difficulty::{Difficulty, PerformanceTracker};
+
+#[test]
+fn difficulty_starts_easy() {
+    let difficulty = Difficulty::new();
+
+    assert_eq!(difficulty.enemy_spawn_interval(), Difficulty::START_SPAWN_INTERVAL);
+    assert_eq!(difficulty.enemy_speed_multiplier(), Difficulty::START_SPEED_MULTIPLIER);
+}
+
+#[test]
+fn difficulty_is_halfway_at_half_ramp() {
+    let mut difficulty = Difficulty::new();
+    difficulty.update(Difficulty::RAMP_DURATION / 2.0);
+
+    assert!((difficulty.enemy_spawn_interval() - 1.2).abs() < 0.001);
+    assert!((difficulty.enemy_speed_multiplier() - 1.75).abs() < 0.001);
+}
+
+#[test]
+fn difficulty_is_capped() {
+    let mut difficulty = Difficulty::new();
+    difficulty.update(Difficulty::RAMP_DURATION * 100.0);
+
+    assert_eq!(difficulty.enemy_spawn_interval(), Difficulty::MIN_SPAWN_INTERVAL);
+    assert_eq!(difficulty.enemy_speed_multiplier(), Difficulty::MAX_SPEED_MULTIPLIER);
+}
+
+#[test]
+fn difficulty_ramps_monotonically() {
+    let mut difficulty = Difficulty::new();
+    let mut last_interval = difficulty.enemy_spawn_interval();
+    let mut last_multiplier = difficulty.enemy_speed_multiplier();
+
+    for _ in 0..200 {
+        difficulty.update(1.0);
+        assert!(difficulty.enemy_spawn_interval() <= last_interval);
+        assert!(difficulty.enemy_speed_multiplier() >= last_multiplier);
+        assert!(difficulty.enemy_spawn_interval() >= Difficulty::MIN_SPAWN_INTERVAL);
+        assert!(difficulty.enemy_speed_multiplier() <= Difficulty::MAX_SPEED_MULTIPLIER);
+
+        last_interval = difficulty.enemy_spawn_interval();
+        last_multiplier = difficulty.enemy_speed_multiplier();
+    }
+}
+
+#[test]
+fn kill_streak_raises_multiplier() {
+    let mut tracker = PerformanceTracker::new();
+    assert_eq!(tracker.multiplier(), 1.0);
+
+    for _ in 0..10 {
+        tracker.update(1.0);
+        tracker.record_kill();
+    }
+    assert!(tracker.multiplier() > 1.0);
+    assert!(tracker.multiplier() <= 1.0 + PerformanceTracker::MAX_ADJUSTMENT);
+}
+
+#[test]
+fn death_lowers_multiplier_until_it_leaves_the_window() {
+    let mut tracker = PerformanceTracker::new();
+    for _ in 0..10 {
+        tracker.record_kill();
+    }
+
+    tracker.record_death();
+    assert!(tracker.multiplier() < 1.0);
+
+    tracker.update(PerformanceTracker::WINDOW + 1.0);
+    assert_eq!(tracker.multiplier(), 1.0);
+}
+
+#[test]
+fn doing_well_spawns_enemies_faster() {
+    let mut difficulty = Difficulty::new();
+    let normal = difficulty.enemy_spawn_interval();
+
+    for _ in 0..20 {
+        difficulty.performance.record_kill();
+    }
+    assert!(difficulty.enemy_spawn_interval() < normal);
+
+    difficulty.performance.record_death();
+    assert!(difficulty.enemy_spawn_interval() > normal);
+}
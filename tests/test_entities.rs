@@ -1,22 +1,15 @@
-use ggez::mint::Point2;
-use ggez::{graphics, Context};
+use ggez::mint::{Point2, Vector2};
+use ggez::graphics;
 use quickcheck_macros::quickcheck;
 
 use shooter::entities::*;
-use shooter::assets::Sprite;
+use shooter::assets::{skin_images, Skin, SkinImages};
+use shooter::collision::Collidable;
+use shooter::movement::{SeekPlayer, SineWave};
+use shooter::screen::ScreenConfig;
 
-#[derive(Debug)]
-struct MockSprite {
-    width: f32,
-    height: f32,
-}
-
-impl Sprite for MockSprite {
-    fn draw(&mut self, _center: Point2<f32>, _canvas: &mut graphics::Canvas) {}
-
-    fn width(&self, _ctx: &mut Context) -> f32 { self.width }
-    fn height(&self, _ctx: &mut Context) -> f32 { self.height }
-}
+mod common;
+use common::MockSprite;
 
 #[quickcheck]
 fn prop_enemies_fall_downwards(x: f32) -> bool {
@@ -26,9 +19,9 @@ fn prop_enemies_fall_downwards(x: f32) -> bool {
     };
 
     let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0 });
-    let mut enemy = Enemy::new("test", enemy_pos, 10.0, mock_sprite).unwrap();
+    let mut enemy = Enemy::new("test", enemy_pos, 10.0, 1, 1, mock_sprite).unwrap();
 
-    let old_pos = enemy.pos.clone();
+    let old_pos = enemy.pos;
     enemy.update(10.0);
 
     enemy.pos.x == old_pos.x && enemy.pos.y > old_pos.y
@@ -39,10 +32,1172 @@ fn prop_shots_fly_upwards(x: u32, y: u32) -> bool {
     let mut shot = Shot::new(Point2 {
         x: (x as f32) / 1000.0,
         y: (y as f32) / 1000.0,
-    });
+    }, Shot::direction_velocity(0.0));
 
-    let old_pos = shot.pos.clone();
+    let old_pos = shot.pos;
     shot.update(10.0);
 
     shot.pos.x == old_pos.x && shot.pos.y < old_pos.y
 }
+
+#[test]
+fn player_diagonal_movement_is_normalized() {
+    let mut player = Player::new(Point2 { x: 1000.0, y: 1000.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 2000.0, 2000.0);
+
+    for _ in 0..60 {
+        player.update(Vector2 { x: 1.0, y: 1.0 }, 1.0 / 60.0, play_area);
+    }
+
+    let expected = Player::SPEED / f32::sqrt(2.0);
+    assert!((player.velocity.x - expected).abs() < 0.01);
+    assert!((player.velocity.y - expected).abs() < 0.01);
+}
+
+#[test]
+fn player_drifts_after_input_stops() {
+    let mut player = Player::new(Point2 { x: 1000.0, y: 1000.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 2000.0, 2000.0);
+
+    for _ in 0..60 {
+        player.update(Vector2 { x: 1.0, y: 0.0 }, 1.0 / 60.0, play_area);
+    }
+
+    for _ in 0..5 {
+        let old_x = player.pos.x;
+        player.update(Vector2 { x: 0.0, y: 0.0 }, 1.0 / 60.0, play_area);
+        assert!(player.pos.x > old_x);
+    }
+
+    for _ in 0..60 {
+        player.update(Vector2 { x: 0.0, y: 0.0 }, 1.0 / 60.0, play_area);
+    }
+    assert_eq!(player.velocity.x, 0.0);
+}
+
+#[test]
+fn player_stays_in_play_area() {
+    let mut player = Player::new(Point2 { x: 600.0, y: 1000.0 });
+    let play_area = Player::play_area(&ScreenConfig::new(1200.0, 1000.0));
+
+    for _ in 0..10 {
+        player.update(Vector2 { x: 0.0, y: -1.0 }, 1.0, play_area);
+    }
+    assert_eq!(player.pos.y, play_area.top());
+
+    for _ in 0..10 {
+        player.update(Vector2 { x: 1.0, y: 1.0 }, 1.0, play_area);
+    }
+    assert_eq!(player.pos.x, 1200.0);
+    assert_eq!(player.pos.y, 1000.0);
+}
+
+#[test]
+fn player_loses_life_when_health_runs_out() {
+    let mut player = Player::new(Point2 { x: 0.0, y: 0.0 });
+
+    player.take_damage(Player::MAX_HEALTH - 1);
+    assert_eq!(player.health, 1);
+    assert_eq!(player.lives, Player::STARTING_LIVES);
+
+    player.take_damage(1);
+    assert_eq!(player.health, Player::MAX_HEALTH);
+    assert_eq!(player.lives, Player::STARTING_LIVES - 1);
+    assert!(player.is_invulnerable());
+}
+
+#[test]
+fn player_ignores_damage_while_invulnerable() {
+    let mut player = Player::new(Point2 { x: 0.0, y: 0.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 100.0, 100.0);
+
+    player.take_damage(Player::MAX_HEALTH);
+    player.take_damage(Player::MAX_HEALTH);
+    assert_eq!(player.lives, Player::STARTING_LIVES - 1);
+
+    player.update(Vector2 { x: 0.0, y: 0.0 }, Player::DEATH_TIME, play_area);
+    player.update(Vector2 { x: 0.0, y: 0.0 }, Player::INVULN_TIME, play_area);
+    assert!(!player.is_invulnerable());
+
+    player.take_damage(Player::MAX_HEALTH);
+    assert_eq!(player.lives, Player::STARTING_LIVES - 2);
+}
+
+#[test]
+fn player_dies_after_last_life() {
+    let mut player = Player::new(Point2 { x: 0.0, y: 0.0 });
+    player.lives = 1;
+
+    player.take_damage(Player::MAX_HEALTH);
+    assert!(player.is_dead());
+    assert!(!player.is_invulnerable());
+}
+
+#[test]
+fn shot_despawns_after_leaving_screen() {
+    let mut shot = Shot::new(Point2 { x: 100.0, y: 10.0 }, Shot::direction_velocity(0.0));
+
+    shot.update(0.04);
+    assert!(shot.is_alive);
+
+    shot.update(1.0);
+    assert!(!shot.is_alive);
+}
+
+fn test_enemy(pos: Point2<f32>) -> Enemy {
+    let mock_sprite = Box::new(MockSprite { width: 100.0, height: 50.0 });
+    Enemy::new("test", pos, 10.0, 1, 1, mock_sprite).unwrap()
+}
+
+#[test]
+fn shot_hits_enemy_dead_center() {
+    let enemy = test_enemy(Point2 { x: 100.0, y: 100.0 });
+    let shot = Shot::new(Point2 { x: 142.0, y: 117.0 }, Shot::direction_velocity(0.0));
+
+    assert!(shot.collides_with(&enemy));
+}
+
+#[test]
+fn shot_just_outside_enemy_misses() {
+    let enemy = test_enemy(Point2 { x: 100.0, y: 100.0 });
+    let shot = Shot::new(Point2 { x: 201.0, y: 117.0 }, Shot::direction_velocity(0.0));
+
+    assert!(!shot.collides_with(&enemy));
+}
+
+#[test]
+fn shot_above_enemy_misses() {
+    let enemy = test_enemy(Point2 { x: 100.0, y: 100.0 });
+    let shot = Shot::new(Point2 { x: 142.0, y: 100.0 - Shot::COLLISION_SIZE - 1.0 }, Shot::direction_velocity(0.0));
+
+    assert!(!shot.collides_with(&enemy));
+}
+
+#[test]
+fn circle_collision_rejects_corner_hits() {
+    let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0 });
+    let mut enemy = Enemy::new("test", Point2 { x: 100.0, y: 100.0 }, 10.0, 1, 1, mock_sprite).unwrap();
+    let shot = Shot::new(Point2 { x: 190.0, y: 190.0 }, Shot::direction_velocity(0.0));
+
+    assert!(shot.hits(&enemy));
+
+    enemy.collision_shape = CollisionShape::Circle;
+    assert!(!shot.hits(&enemy));
+}
+
+#[test]
+fn circle_collision_accepts_center_hits() {
+    let mock_sprite = Box::new(MockSprite { width: 100.0, height: 100.0 });
+    let mut enemy = Enemy::new("test", Point2 { x: 100.0, y: 100.0 }, 10.0, 1, 1, mock_sprite).unwrap();
+    enemy.collision_shape = CollisionShape::Circle;
+
+    let (center, radius) = enemy.bounding_circle();
+    assert_eq!((center.x, center.y, radius), (150.0, 150.0, 50.0));
+
+    let shot = Shot::new(Point2 { x: 145.0, y: 190.0 }, Shot::direction_velocity(0.0));
+    assert!(shot.collides_with_circle(&enemy));
+}
+
+#[test]
+fn tanky_enemy_takes_several_hits() {
+    let mock_sprite = Box::new(MockSprite { width: 100.0, height: 50.0 });
+    let mut enemy = Enemy::new("test", Point2 { x: 0.0, y: 0.0 }, 10.0, 3, 1, mock_sprite).unwrap();
+
+    assert!(!enemy.hit(1));
+    assert!(!enemy.hit(1));
+    assert!(enemy.is_alive);
+
+    assert!(enemy.hit(1));
+    assert!(!enemy.is_alive);
+}
+
+#[test]
+fn enemy_past_bottom_edge_escapes() {
+    let mut enemy = test_enemy(Point2 { x: 100.0, y: 1001.0 });
+
+    assert!(enemy.update_with_bounds(0.01, 1000.0, 1000.0));
+    assert!(!enemy.is_alive);
+}
+
+#[test]
+fn enemy_on_screen_does_not_escape() {
+    let mut enemy = test_enemy(Point2 { x: 100.0, y: 500.0 });
+
+    assert!(!enemy.update_with_bounds(0.01, 1000.0, 1000.0));
+    assert!(enemy.is_alive);
+}
+
+#[test]
+fn single_weapon_fires_one_shot_straight_up() {
+    let player = Player::new(Point2 { x: 500.0, y: 500.0 });
+
+    let shots = player.spawn_shots();
+    assert_eq!(shots.len(), 1);
+    assert_eq!(shots[0].velocity().x, 0.0);
+    assert_eq!(shots[0].velocity().y, -Shot::SPEED);
+}
+
+#[test]
+fn spread_weapon_fires_three_symmetric_shots() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    player.weapon_mode = WeaponMode::Spread;
+
+    let shots = player.spawn_shots();
+    assert_eq!(shots.len(), 3);
+    assert!(shots[0].velocity().x < 0.0);
+    assert_eq!(shots[1].velocity().x, 0.0);
+    assert_eq!(shots[0].velocity().x, -shots[2].velocity().x);
+    assert!(shots.iter().all(|shot| shot.velocity().y < 0.0));
+}
+
+#[test]
+fn enemy_follows_movement_pattern() {
+    let mut enemy = test_enemy(Point2 { x: 100.0, y: 0.0 }).
+        with_movement(Box::new(SineWave { amplitude: 50.0, period: 2.0 }));
+
+    for _ in 0..30 {
+        enemy.update(1.0 / 60.0);
+    }
+
+    assert!((enemy.pos.x - 150.0).abs() < 0.01);
+    assert!((enemy.pos.y - 5.0).abs() < 0.01);
+}
+
+#[test]
+fn shooting_enemy_fires_at_its_interval() {
+    let mut enemy = test_enemy(Point2 { x: 100.0, y: 100.0 }).with_fire_interval(1.0);
+
+    assert!(enemy.update_shooting(0.5).is_none());
+
+    let shot = enemy.update_shooting(0.5).unwrap();
+    assert!(shot.velocity().y > 0.0);
+    assert!(shot.pos.y >= enemy.bounding_rect().bottom());
+
+    assert!(enemy.update_shooting(0.75).is_none());
+    assert!(enemy.update_shooting(0.25).is_some());
+}
+
+#[test]
+fn regular_enemy_never_fires() {
+    let mut enemy = test_enemy(Point2 { x: 100.0, y: 100.0 });
+
+    assert!(enemy.update_shooting(100.0).is_none());
+}
+
+#[test]
+fn enemy_shot_damages_player() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    let shot = Shot::new(Point2 { x: 495.0, y: 450.0 }, Vector2 { x: 0.0, y: Shot::ENEMY_SPEED });
+
+    assert!(shot.collides_with_player(&player));
+    player.take_damage(1);
+    assert_eq!(player.health, Player::MAX_HEALTH - 1);
+}
+
+#[test]
+fn shot_cooldown_counts_down_in_update() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 1000.0, 1000.0);
+    let no_input = Vector2 { x: 0.0, y: 0.0 };
+
+    player.update(no_input, Player::SHOT_TIMEOUT, play_area);
+    assert!(player.can_shoot());
+
+    player.time_until_next_shot = Player::SHOT_TIMEOUT;
+    assert!(!player.can_shoot());
+
+    player.update(no_input, Player::SHOT_TIMEOUT / 2.0, play_area);
+    assert!(!player.can_shoot());
+
+    player.update(no_input, Player::SHOT_TIMEOUT / 2.0, play_area);
+    assert!(player.can_shoot());
+    assert_eq!(player.time_until_next_shot, 0.0);
+}
+
+#[test]
+fn clamped_player_stops_at_right_edge() {
+    let mut player = Player::new(Point2 { x: 990.0, y: 500.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 1000.0, 1000.0);
+    player.velocity.x = Player::SPEED;
+
+    for _ in 0..60 {
+        player.update(Vector2 { x: 1.0, y: 0.0 }, 1.0 / 60.0, play_area);
+    }
+    assert_eq!(player.pos.x, 1000.0);
+}
+
+#[test]
+fn wrapping_player_reenters_on_the_left() {
+    let mut player = Player::new(Point2 { x: 990.0, y: 500.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 1000.0, 1000.0);
+    player.movement_bounds = MovementBounds::Wrap;
+    player.velocity.x = Player::SPEED;
+
+    // Still partly visible, so it keeps going past the edge
+    player.update(Vector2 { x: 1.0, y: 0.0 }, 0.1, play_area);
+    assert!(player.pos.x > 1000.0);
+
+    while player.pos.x > 500.0 {
+        player.update(Vector2 { x: 1.0, y: 0.0 }, 1.0 / 60.0, play_area);
+    }
+    assert!(player.pos.x < 0.0);
+    assert!(player.pos.x + Player::WIDTH / 2.0 >= 0.0);
+    assert_eq!(player.velocity.x, Player::SPEED);
+}
+
+#[test]
+fn charge_accumulates_while_held() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    assert_eq!(player.charge_damage(), 1);
+
+    for _ in 0..50 {
+        player.charge_up(1.0 / 60.0);
+    }
+    assert!((player.charge - 50.0 / 60.0).abs() < 0.001);
+    assert_eq!(player.charge_damage(), 2);
+
+    for _ in 0..300 {
+        player.charge_up(1.0 / 60.0);
+    }
+    assert_eq!(player.charge, Player::MAX_CHARGE);
+    assert_eq!(player.charge_damage(), 3);
+}
+
+#[test]
+fn released_charge_fires_stronger_shots() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    player.charge_up(Player::MAX_CHARGE);
+
+    let shots = player.release_charge();
+    assert_eq!(shots[0].damage, 3);
+    assert_eq!(shots[0].scale, 2.0);
+    assert_eq!(player.charge, 0.0);
+
+    let shots = player.release_charge();
+    assert_eq!(shots[0].damage, 1);
+    assert_eq!(shots[0].scale, 1.0);
+}
+
+#[test]
+fn enemy_flashes_after_non_fatal_hit() {
+    let mock_sprite = Box::new(MockSprite { width: 100.0, height: 50.0 });
+    let mut enemy = Enemy::new("test", Point2 { x: 0.0, y: 0.0 }, 10.0, 2, 1, mock_sprite).unwrap();
+    assert_eq!(enemy.hit_flash_timer, 0.0);
+
+    enemy.hit(1);
+    assert_eq!(enemy.hit_flash_timer, Enemy::HIT_FLASH_TIME);
+
+    enemy.update(Enemy::HIT_FLASH_TIME / 2.0);
+    assert!(enemy.hit_flash_timer > 0.0);
+
+    enemy.update(Enemy::HIT_FLASH_TIME);
+    assert_eq!(enemy.hit_flash_timer, 0.0);
+}
+
+#[test]
+fn powerup_collides_with_player() {
+    let player = Player::new(Point2 { x: 400.0, y: 600.0 });
+
+    let touching = Powerup::new(Point2 { x: 390.0, y: 540.0 }, PowerupKind::Spread);
+    assert!(touching.collides_with_player(&player));
+
+    let far_away = Powerup::new(Point2 { x: 390.0, y: 100.0 }, PowerupKind::Spread);
+    assert!(!far_away.collides_with_player(&player));
+}
+
+#[test]
+fn extra_life_is_granted_once() {
+    let mut player = Player::new(Point2 { x: 400.0, y: 600.0 });
+    let mut powerup = Powerup::new(Point2 { x: 390.0, y: 540.0 }, PowerupKind::ExtraLife);
+
+    assert!(powerup.collect(&mut player));
+    assert!(!powerup.is_alive);
+    assert!(!powerup.collect(&mut player));
+    assert_eq!(player.lives, Player::STARTING_LIVES + 1);
+}
+
+#[test]
+fn powerups_apply_weapon_effects() {
+    let mut player = Player::new(Point2 { x: 400.0, y: 600.0 });
+    let pos = Point2 { x: 390.0, y: 540.0 };
+
+    Powerup::new(pos, PowerupKind::Spread).collect(&mut player);
+    assert_eq!(player.weapon_mode, WeaponMode::Spread);
+
+    assert_eq!(player.shot_timeout(), Player::SHOT_TIMEOUT);
+    Powerup::new(pos, PowerupKind::RapidFire).collect(&mut player);
+    assert_eq!(player.shot_timeout(), Player::RAPID_FIRE_TIMEOUT);
+}
+
+#[test]
+fn powerup_despawns_below_screen() {
+    let mut powerup = Powerup::new(Point2 { x: 0.0, y: 590.0 }, PowerupKind::RapidFire);
+
+    powerup.update(0.05, 600.0);
+    assert!(powerup.is_alive);
+    powerup.update(0.1, 600.0);
+    assert!(!powerup.is_alive);
+}
+
+#[test]
+fn player_dies_then_respawns() {
+    let start = Point2 { x: 50.0, y: 100.0 };
+    let mut player = Player::new(start);
+    let play_area = graphics::Rect::new(0.0, 0.0, 100.0, 100.0);
+    let right = Vector2 { x: 1.0, y: 0.0 };
+
+    player.pos = Point2 { x: 80.0, y: 90.0 };
+    player.time_until_next_shot = 0.0;
+    player.take_damage(Player::MAX_HEALTH);
+    assert!(matches!(player.state, PlayerState::Dying));
+    assert!(!player.can_shoot());
+
+    // Input is ignored while dying
+    player.update(right, Player::DEATH_TIME / 2.0, play_area);
+    assert!(matches!(player.state, PlayerState::Dying));
+    assert_eq!(player.pos.x, 80.0);
+
+    player.update(right, Player::DEATH_TIME / 2.0, play_area);
+    assert!(matches!(player.state, PlayerState::Normal));
+    assert_eq!((player.pos.x, player.pos.y), (start.x, start.y));
+    assert!(player.is_invulnerable());
+    assert!(player.can_shoot());
+}
+
+#[test]
+fn knockback_wears_off() {
+    let mut enemy = test_enemy(Point2 { x: 0.0, y: 0.0 }).with_knockback_decay(0.5);
+    let base = enemy.velocity();
+
+    enemy.apply_knockback(Vector2 { x: 0.0, y: -200.0 });
+    assert_eq!(enemy.velocity().y, base.y - 200.0);
+
+    enemy.update(0.25);
+    assert!(enemy.velocity().y < base.y);
+    assert!(enemy.velocity().y > base.y - 200.0);
+
+    enemy.update(0.25);
+    assert_eq!((enemy.velocity().x, enemy.velocity().y), (base.x, base.y));
+}
+
+#[test]
+fn knockback_pushes_enemy_up() {
+    let mut enemy = test_enemy(Point2 { x: 0.0, y: 100.0 });
+
+    enemy.apply_knockback(Vector2 { x: 0.0, y: -Enemy::KNOCKBACK_IMPULSE });
+    enemy.update(1.0 / 60.0);
+    assert!(enemy.pos.y < 100.0);
+}
+
+#[test]
+fn piercing_shot_goes_through_enemies() {
+    let mut enemies: Vec<Enemy> = (0..3).map(|_| test_enemy(Point2 { x: 0.0, y: 0.0 })).collect();
+    let mut shot = Shot::new(Point2 { x: 10.0, y: 10.0 }, Vector2 { x: 0.0, y: -Shot::SPEED }).with_pierce(2);
+
+    assert_eq!(shot.strike(&mut enemies[0]), Some(true));
+    assert!(shot.is_alive);
+    assert_eq!(shot.strike(&mut enemies[1]), Some(true));
+    assert!(shot.is_alive);
+    assert_eq!(shot.strike(&mut enemies[2]), Some(true));
+    assert!(!shot.is_alive);
+}
+
+#[test]
+fn piercing_shot_hits_each_enemy_once() {
+    let mut enemy = test_enemy(Point2 { x: 0.0, y: 0.0 });
+    enemy.health = 3;
+    let mut shot = Shot::new(Point2 { x: 10.0, y: 10.0 }, Vector2 { x: 0.0, y: -Shot::SPEED }).with_pierce(2);
+
+    assert_eq!(shot.strike(&mut enemy), Some(false));
+    assert_eq!(shot.strike(&mut enemy), None);
+    assert_eq!(enemy.health, 2);
+    assert_eq!(shot.pierce_count, 1);
+}
+
+#[test]
+fn normal_shot_dies_on_first_hit() {
+    let mut enemy = test_enemy(Point2 { x: 0.0, y: 0.0 });
+    let mut shot = Shot::new(Point2 { x: 10.0, y: 10.0 }, Vector2 { x: 0.0, y: -Shot::SPEED });
+
+    assert_eq!(shot.strike(&mut enemy), Some(true));
+    assert!(!shot.is_alive);
+    assert_eq!(shot.strike(&mut test_enemy(Point2 { x: 0.0, y: 0.0 })), None);
+}
+
+#[test]
+fn homing_shot_turns_toward_target() {
+    let targets = vec![test_enemy(Point2 { x: 400.0, y: 0.0 })];
+    let mut shot = Shot::new(Point2 { x: 0.0, y: 500.0 }, Shot::direction_velocity(0.0)).with_homing();
+
+    let angle = |shot: &Shot| f32::atan2(shot.velocity().x, -shot.velocity().y);
+    let mut previous = angle(&shot);
+    for _ in 0..10 {
+        shot.home_toward(&targets, 1.0 / 60.0);
+
+        // Turning right, but never faster than the turn rate
+        let current = angle(&shot);
+        assert!(current > previous);
+        assert!(current - previous <= Shot::MAX_TURN_RATE / 60.0 + 0.0001);
+        previous = current;
+
+        let velocity = shot.velocity();
+        let speed = (velocity.x * velocity.x + velocity.y * velocity.y).sqrt();
+        assert!((speed - Shot::SPEED).abs() < 0.01);
+    }
+}
+
+#[test]
+fn non_homing_shot_keeps_going_straight() {
+    let targets = vec![test_enemy(Point2 { x: 400.0, y: 0.0 })];
+    let mut shot = Shot::new(Point2 { x: 0.0, y: 500.0 }, Shot::direction_velocity(0.0));
+
+    shot.home_toward(&targets, 1.0 / 60.0);
+    assert_eq!(shot.velocity().x, 0.0);
+}
+
+#[test]
+fn player_bounding_rect_matches_sprite_offset() {
+    let player = Player::new(Point2 { x: 400.0, y: 600.0 });
+    let rect = player.bounding_rect();
+
+    // Drawn with its bottom-middle at `pos`
+    assert_eq!(rect.x, 400.0 - Player::WIDTH / 2.0);
+    assert_eq!(rect.y, 600.0 - Player::HEIGHT);
+    assert_eq!((rect.w, rect.h), (Player::WIDTH, Player::HEIGHT));
+}
+
+#[test]
+fn enemy_ramming_player_damages_both() {
+    let mut player = Player::new(Point2 { x: 400.0, y: 600.0 });
+    let mut enemy = test_enemy(Point2 { x: 350.0, y: 520.0 });
+    let mut far_enemy = test_enemy(Point2 { x: 350.0, y: 100.0 });
+
+    assert!(!far_enemy.ram(&mut player));
+    assert!(far_enemy.is_alive);
+
+    assert!(enemy.ram(&mut player));
+    assert!(!enemy.is_alive);
+    assert_eq!(player.health, Player::MAX_HEALTH - Enemy::RAM_DAMAGE);
+
+    // Already destroyed, so it can't hit again
+    assert!(!enemy.ram(&mut player));
+    assert_eq!(player.health, Player::MAX_HEALTH - Enemy::RAM_DAMAGE);
+}
+
+#[test]
+fn shoot_respects_cooldown() {
+    let mut player = Player::new(Point2 { x: 400.0, y: 600.0 });
+    player.time_until_next_shot = 0.0;
+
+    let shots = player.shoot();
+    assert_eq!(shots.len(), 1);
+    assert!(player.shoot().is_empty());
+    assert!(matches!(player.state, PlayerState::Shooting));
+    assert_eq!(player.time_until_next_shot, Player::SHOT_TIMEOUT);
+
+    let shot = &shots[0];
+    assert!(shot.pos.y < player.pos.y);
+    assert!(shot.velocity().y < 0.0);
+}
+
+#[test]
+fn shooting_pose_only_lasts_briefly() {
+    let mut player = Player::new(Point2 { x: 400.0, y: 600.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 1000.0, 1000.0);
+    let no_input = Vector2 { x: 0.0, y: 0.0 };
+    player.time_until_next_shot = 0.0;
+
+    player.shoot();
+    assert!(matches!(player.state, PlayerState::Shooting));
+
+    player.update(no_input, Player::SHOOT_ANIM_TIME / 2.0, play_area);
+    assert!(matches!(player.state, PlayerState::Shooting));
+
+    player.update(no_input, Player::SHOOT_ANIM_TIME / 2.0, play_area);
+    assert!(matches!(player.state, PlayerState::Normal));
+}
+
+#[test]
+fn bombs_run_out() {
+    let mut player = Player::new(Point2 { x: 0.0, y: 0.0 });
+
+    for remaining in (0..Player::STARTING_BOMBS).rev() {
+        assert!(player.use_bomb());
+        assert_eq!(player.bombs, remaining);
+    }
+    assert!(!player.use_bomb());
+    assert_eq!(player.bombs, 0);
+}
+
+#[test]
+fn bomb_clears_enemies_and_shots() {
+    let mut enemies: Vec<Enemy> = (0..3).map(|_| test_enemy(Point2 { x: 0.0, y: 0.0 })).collect();
+    for enemy in enemies.iter_mut() {
+        enemy.point_value = 10;
+    }
+    enemies[2].is_alive = false;
+    let mut shots = vec![Shot::new(Point2 { x: 0.0, y: 0.0 }, Vector2 { x: 0.0, y: Shot::ENEMY_SPEED })];
+
+    let points = detonate_bomb(&mut enemies, &mut shots, 0.5);
+    assert_eq!(points, 10);
+    assert!(enemies.iter().all(|enemy| !enemy.is_alive));
+    assert!(!shots[0].is_alive);
+}
+
+#[test]
+fn enemy_kinds_have_documented_stats() {
+    let expected = [
+        (EnemyKind::Grunt, 1, 120.0, 1),
+        (EnemyKind::Tank, 5, 60.0, 5),
+        (EnemyKind::Fast, 1, 250.0, 3),
+        (EnemyKind::Boss, 30, 30.0, 50),
+        (EnemyKind::Splitter, 2, 80.0, 4),
+        (EnemyKind::Shard, 1, 200.0, 1),
+    ];
+
+    for (kind, health, speed, point_value) in expected {
+        let stats = kind.stats();
+        assert_eq!((stats.health, stats.speed, stats.point_value), (health, speed, point_value));
+
+        let sprite = Box::new(MockSprite { width: 100.0, height: 50.0 });
+        let enemy = kind.build(stats.label, Point2 { x: 0.0, y: 0.0 }, stats.speed, sprite).unwrap();
+        assert_eq!(enemy.health, health);
+        assert_eq!(enemy.point_value, point_value);
+        assert_eq!(enemy.base_velocity().y, speed);
+        assert_eq!(enemy.label(), stats.label);
+    }
+}
+
+#[test]
+fn player_tilts_into_movement() {
+    let mut player = Player::new(Point2 { x: 1000.0, y: 1000.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 2000.0, 2000.0);
+
+    let mut previous = player.tilt;
+    for _ in 0..30 {
+        player.update(Vector2 { x: 1.0, y: 0.0 }, 1.0 / 60.0, play_area);
+        assert!(player.tilt >= previous);
+        assert!(player.tilt <= Player::MAX_TILT);
+        previous = player.tilt;
+    }
+    assert!(player.tilt > Player::MAX_TILT * 0.9);
+
+    for _ in 0..60 {
+        player.update(Vector2 { x: 0.0, y: 0.0 }, 1.0 / 60.0, play_area);
+    }
+    assert!(player.tilt.abs() < 0.01);
+}
+
+#[test]
+fn releasing_fire_produces_shot_events() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    player.time_until_next_shot = 0.0;
+
+    assert!(player.update_weapon(true, 0.1).is_empty());
+    assert!(matches!(player.state, PlayerState::Shooting));
+
+    let events = player.update_weapon(false, 0.1);
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|event| matches!(event, PlayerEvent::FiredShot(_))));
+
+    // Still cooling down, so another press and release fires nothing
+    player.update_weapon(true, 0.0);
+    assert!(player.update_weapon(false, 0.0).is_empty());
+}
+
+#[test]
+fn player_reports_respawn_after_dying() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 1000.0, 1000.0);
+    player.health = 1;
+    player.take_damage(1);
+    assert!(player.is_dying());
+
+    assert!(player.update_weapon(true, 0.1).is_empty());
+    assert!(player.update(Vector2 { x: 0.0, y: 0.0 }, Player::DEATH_TIME / 2.0, play_area).is_none());
+    assert!(matches!(player.update(Vector2 { x: 0.0, y: 0.0 }, Player::DEATH_TIME, play_area), Some(PlayerEvent::Respawned)));
+}
+
+#[test]
+fn enemy_bounces_off_the_right_wall() {
+    let mut enemy = test_enemy(Point2 { x: 890.0, y: 100.0 }).with_velocity(Vector2 { x: 40.0, y: 10.0 });
+
+    // The sprite is 100 wide, so its right edge reaches the wall at x = 900. It would have gone
+    // 10 past that, so it ends up 10 back from the wall.
+    assert!(!enemy.update_with_bounds(0.5, 1000.0, 1000.0));
+    assert_eq!(enemy.pos.x, 890.0);
+    assert_eq!(enemy.velocity().x, -40.0);
+    assert_eq!(enemy.velocity().y, 10.0);
+
+    enemy.update_with_bounds(0.5, 1000.0, 1000.0);
+    assert!(enemy.pos.x < 900.0);
+    assert!(enemy.bounding_rect().right() <= 1000.0);
+}
+
+#[test]
+fn enemy_bounces_off_the_left_wall() {
+    let mut enemy = test_enemy(Point2 { x: 10.0, y: 100.0 }).with_velocity(Vector2 { x: -40.0, y: 0.0 });
+
+    enemy.update_with_bounds(0.5, 1000.0, 1000.0);
+    assert_eq!(enemy.pos.x, 10.0);
+    assert_eq!(enemy.velocity().x, 40.0);
+}
+
+#[test]
+fn sine_wave_enemy_swings_back_off_the_wall() {
+    let sine = SineWave { amplitude: 100.0, period: 4.0 };
+    let mut enemy = test_enemy(Point2 { x: 850.0, y: 100.0 }).with_movement(Box::new(sine));
+
+    // Left alone the wave would peak at x = 950, 50 past the wall, so it's mirrored back to 850
+    let mut reached_wall = false;
+    for _ in 0..60 {
+        enemy.update_with_bounds(1.0 / 60.0, 1000.0, 1000.0);
+        assert!(enemy.pos.x >= 0.0 && enemy.bounding_rect().right() <= 1000.0);
+        reached_wall |= enemy.pos.x > 890.0;
+    }
+    assert!(reached_wall);
+    assert!((enemy.pos.x - 850.0).abs() < 0.01);
+
+    // Past the peak the wave heads left, which the bounce turned into heading right
+    enemy.update_with_bounds(1.0 / 60.0, 1000.0, 1000.0);
+    assert!(enemy.pos.x > 850.0);
+}
+
+#[test]
+fn enemy_step_reports_shots_and_escape() {
+    let mut enemy = test_enemy(Point2 { x: 100.0, y: 100.0 }).with_fire_interval(1.0);
+
+    let events = enemy.step(1.0, 800.0, 1000.0);
+    assert!(events.iter().any(|event| matches!(event, EnemyEvent::FiredShot(_))));
+    assert!(!events.iter().any(|event| matches!(event, EnemyEvent::ReachedBottom)));
+
+    let events = enemy.step(1000.0, 800.0, 1000.0);
+    assert!(events.iter().any(|event| matches!(event, EnemyEvent::ReachedBottom)));
+}
+
+#[test]
+fn shot_trail_keeps_last_positions_in_order() {
+    let mut shot = Shot::new(Point2 { x: 0.0, y: 1000.0 }, Vector2 { x: 0.0, y: -10.0 });
+    assert_eq!(shot.trail().count(), 0);
+
+    for _ in 0..Shot::TRAIL_LENGTH + 3 {
+        shot.update(1.0);
+    }
+
+    let trail: Vec<f32> = shot.trail().map(|pos| pos.y).collect();
+    let expected: Vec<f32> = (1..=Shot::TRAIL_LENGTH).rev().map(|i| shot.pos.y + 10.0 * i as f32).collect();
+    assert_eq!(trail, expected);
+}
+
+#[test]
+fn swept_test_catches_shot_jumping_over_enemy() {
+    let mut enemy = test_enemy(Point2 { x: 100.0, y: 100.0 });
+    let prev_pos = Point2 { x: 142.0, y: 200.0 };
+    let mut shot = Shot::new(prev_pos, Shot::direction_velocity(0.0));
+
+    // A low frame rate moves the shot from below the enemy to above it in one step
+    shot.update(0.4);
+    assert!(!shot.collides_with(&enemy));
+    assert!(shot.swept_collides(prev_pos, &enemy));
+
+    assert_eq!(shot.strike(&mut enemy), Some(true));
+}
+
+#[test]
+fn swept_test_misses_enemy_off_to_the_side() {
+    let enemy = test_enemy(Point2 { x: 100.0, y: 100.0 });
+    let prev_pos = Point2 { x: 300.0, y: 200.0 };
+    let mut shot = Shot::new(prev_pos, Shot::direction_velocity(0.0));
+
+    shot.update(0.4);
+    assert!(!shot.swept_collides(prev_pos, &enemy));
+}
+
+#[test]
+fn splitter_breaks_into_two_children_on_death() {
+    let split = Split::new(Split::COUNT, Split::CHILD_SPEED, || Box::new(MockSprite { width: 40.0, height: 20.0 }));
+    let mut splitter = test_enemy(Point2 { x: 100.0, y: 100.0 }).with_split(split);
+    splitter.hit(1);
+
+    let children = splitter.on_death();
+    assert_eq!(children.len(), 2);
+
+    // Centered on where the splitter was, fanning out to either side and not splitting again
+    assert_eq!((children[0].pos.x, children[0].pos.y), (130.0, 115.0));
+    assert!(children[0].velocity().x < 0.0 && children[1].velocity().x > 0.0);
+    assert!(children.iter().all(|child| child.velocity().y > 0.0));
+    assert!(children.iter().all(|child| child.on_death().is_empty()));
+}
+
+#[test]
+fn grunt_leaves_nothing_behind() {
+    let mut grunt = test_enemy(Point2 { x: 100.0, y: 100.0 });
+    grunt.hit(1);
+
+    assert!(grunt.on_death().is_empty());
+}
+
+#[test]
+fn split_count_and_speed_are_configurable() {
+    let split = Split::new(3, 50.0, || Box::new(MockSprite { width: 10.0, height: 10.0 }));
+    let children = test_enemy(Point2 { x: 0.0, y: 0.0 }).with_split(split).on_death();
+
+    assert_eq!(children.len(), 3);
+    for child in children {
+        let velocity = child.velocity();
+        assert!((velocity.x.hypot(velocity.y) - 50.0).abs() < 0.001);
+    }
+}
+
+#[test]
+fn fire_pressed_during_cooldown_goes_off_when_it_ends() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 1000.0, 1000.0);
+    let still = Vector2 { x: 0.0, y: 0.0 };
+
+    // Pressed just before the cooldown is over
+    player.update(still, Player::SHOT_TIMEOUT - 0.05, play_area);
+    player.update_weapon(true, 0.0);
+    assert!(player.update_weapon(false, 0.0).is_empty());
+
+    player.update(still, 0.1, play_area);
+    let events = player.update_weapon(false, 0.0);
+    assert!(events.iter().any(|event| matches!(event, PlayerEvent::FiredShot(_))));
+
+    // The buffered press was used up by that shot
+    player.update(still, Player::SHOT_TIMEOUT, play_area);
+    assert!(player.update_weapon(false, 0.0).is_empty());
+}
+
+#[test]
+fn fire_buffer_expires() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 1000.0, 1000.0);
+    let still = Vector2 { x: 0.0, y: 0.0 };
+
+    assert!(player.shoot().is_empty());
+    assert_eq!(player.fire_buffer, Player::FIRE_BUFFER_TIME);
+
+    // Pressed too long before the cooldown ended
+    player.update(still, Player::FIRE_BUFFER_TIME, play_area);
+    player.update(still, Player::SHOT_TIMEOUT, play_area);
+    assert!(player.update_weapon(false, 0.0).is_empty());
+}
+
+#[test]
+fn seeking_enemy_moves_toward_player_on_its_right() {
+    let mut enemy = test_enemy(Point2 { x: 100.0, y: 100.0 }).with_movement(Box::new(SeekPlayer::default()));
+    let start_x = enemy.pos.x;
+
+    for _ in 0..60 {
+        enemy.track(Point2 { x: 800.0, y: 900.0 });
+        enemy.update(1.0 / 60.0);
+    }
+    assert!(enemy.pos.x > start_x);
+    assert!(enemy.pos.x - start_x <= SeekPlayer::MAX_SPEED);
+}
+
+#[test]
+fn emptying_the_magazine_blocks_shooting_until_reloaded() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 1000.0, 1000.0);
+    let still = Vector2 { x: 0.0, y: 0.0 };
+
+    for _ in 0..Player::MAG_SIZE {
+        player.update(still, Player::SHOT_TIMEOUT, play_area);
+        assert!(!player.shoot().is_empty());
+    }
+    assert_eq!(player.ammo, 0);
+    assert!(player.is_reloading());
+
+    player.update(still, Player::RELOAD_TIME / 2.0, play_area);
+    assert!(player.shoot().is_empty());
+
+    player.update(still, Player::RELOAD_TIME / 2.0, play_area);
+    assert!(!player.is_reloading());
+    assert_eq!(player.ammo, Player::MAG_SIZE);
+    assert!(!player.shoot().is_empty());
+}
+
+#[test]
+fn each_enemy_kind_has_its_own_marker() {
+    let markers: std::collections::HashSet<MarkerShape> = EnemyKind::ALL.iter().map(|kind| kind.marker()).collect();
+    assert_eq!(markers.len(), EnemyKind::ALL.len());
+}
+
+#[test]
+fn built_enemies_remember_their_kind() {
+    for kind in EnemyKind::ALL {
+        let sprite = Box::new(MockSprite { width: 100.0, height: 50.0 });
+        let enemy = kind.build("test", Point2 { x: 0.0, y: 0.0 }, 10.0, sprite).unwrap();
+        assert_eq!(enemy.kind(), kind);
+
+        // Inside the sprite, in the same corner for every kind
+        let center = enemy.marker_center();
+        assert_eq!((center.x, center.y), (100.0 - MarkerShape::SIZE / 2.0, MarkerShape::SIZE / 2.0));
+    }
+}
+
+#[test]
+fn spawned_enemies_ease_up_to_full_speed() {
+    let mut enemy = test_enemy(Point2 { x: 100.0, y: 100.0 }).with_entry_duration(0.5);
+    assert_eq!(enemy.velocity().y, 0.0);
+
+    enemy.update(0.05);
+    assert!(enemy.velocity().y < enemy.base_velocity().y * 0.1);
+
+    let mut last = enemy.velocity().y;
+    for _ in 0..9 {
+        enemy.update(0.05);
+        assert!(enemy.velocity().y >= last);
+        last = enemy.velocity().y;
+    }
+    assert_eq!(enemy.velocity().y, enemy.base_velocity().y);
+}
+
+#[test]
+fn enemies_without_entry_start_at_full_speed() {
+    let enemy = test_enemy(Point2 { x: 100.0, y: 100.0 });
+
+    assert_eq!(enemy.velocity().y, enemy.base_velocity().y);
+}
+
+#[test]
+fn head_on_shots_cancel_each_other() {
+    let mut player_shots = [Shot::new(Point2 { x: 100.0, y: 100.0 }, Shot::direction_velocity(0.0))];
+    let mut enemy_shots = [
+        Shot::new(Point2 { x: 100.0, y: 90.0 }, Vector2 { x: 0.0, y: Shot::ENEMY_SPEED }),
+        Shot::new(Point2 { x: 500.0, y: 90.0 }, Vector2 { x: 0.0, y: Shot::ENEMY_SPEED }),
+    ];
+
+    assert_eq!(cancel_shots(player_shots.iter_mut(), &mut enemy_shots), 1);
+    assert!(!player_shots[0].is_alive);
+    assert!(!enemy_shots[0].is_alive);
+    assert!(enemy_shots[1].is_alive);
+}
+
+#[test]
+fn piercing_shot_survives_cancelling() {
+    let mut player_shots = [Shot::new(Point2 { x: 100.0, y: 100.0 }, Shot::direction_velocity(0.0)).with_pierce(1)];
+    let mut enemy_shots = [
+        Shot::new(Point2 { x: 100.0, y: 90.0 }, Vector2 { x: 0.0, y: Shot::ENEMY_SPEED }),
+        Shot::new(Point2 { x: 100.0, y: 110.0 }, Vector2 { x: 0.0, y: Shot::ENEMY_SPEED }),
+    ];
+
+    assert_eq!(cancel_shots(player_shots.iter_mut(), &mut enemy_shots), 2);
+    assert!(!player_shots[0].is_alive);
+    assert!(enemy_shots.iter().all(|shot| !shot.is_alive));
+
+    let mut player_shots = [Shot::new(Point2 { x: 100.0, y: 100.0 }, Shot::direction_velocity(0.0)).with_pierce(1)];
+    let mut enemy_shots = [Shot::new(Point2 { x: 100.0, y: 90.0 }, Vector2 { x: 0.0, y: Shot::ENEMY_SPEED })];
+    cancel_shots(player_shots.iter_mut(), &mut enemy_shots);
+    assert!(player_shots[0].is_alive);
+    assert_eq!(player_shots[0].pierce_count, 0);
+}
+
+#[test]
+fn shot_passing_close_by_grazes_once() {
+    let player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    let hit_box = player.bounding_rect();
+
+    // Falls straight down just to the right of the hit box
+    let mut shot = Shot::new(Point2 { x: hit_box.right() + 5.0, y: hit_box.top() - 100.0 }, Vector2 { x: 0.0, y: Shot::ENEMY_SPEED });
+    let mut grazes = 0;
+    for _ in 0..120 {
+        shot.update(1.0 / 60.0);
+        assert!(!shot.collides_with_player(&player));
+        if player.graze(&mut shot) {
+            grazes += 1;
+        }
+    }
+
+    assert_eq!(grazes, 1);
+}
+
+#[test]
+fn far_away_or_direct_hits_dont_graze() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    let hit_box = player.bounding_rect();
+
+    let mut far = Shot::new(Point2 { x: hit_box.right() + Player::GRAZE_MARGIN + 1.0, y: hit_box.y }, Vector2 { x: 0.0, y: 0.0 });
+    assert!(!player.graze(&mut far));
+
+    let mut direct = Shot::new(Point2 { x: hit_box.x + 10.0, y: hit_box.y + 10.0 }, Vector2 { x: 0.0, y: 0.0 });
+    assert!(!player.graze(&mut direct));
+
+    let mut close = Shot::new(Point2 { x: hit_box.right() + 1.0, y: hit_box.y }, Vector2 { x: 0.0, y: 0.0 });
+    player.invuln_timer = 1.0;
+    assert!(!player.graze(&mut close));
+}
+
+#[test]
+fn laser_goes_from_telegraph_to_active_to_done() {
+    let mut laser = Laser::new(500.0, 1.0, 0.5);
+    assert_eq!(laser.phase(), LaserPhase::Telegraph);
+
+    laser.update(0.75);
+    assert_eq!(laser.phase(), LaserPhase::Telegraph);
+
+    // The rest of the frame carries over into the beam
+    laser.update(0.5);
+    assert_eq!(laser.phase(), LaserPhase::Active);
+    assert_eq!(laser.active_timer, 0.25);
+
+    laser.update(0.25);
+    assert_eq!(laser.phase(), LaserPhase::Done);
+}
+
+#[test]
+fn laser_only_hurts_while_active() {
+    let player = Player::new(Point2 { x: 500.0, y: 1000.0 });
+    let mut laser = Laser::new(500.0, 1.0, 0.5);
+    assert!(!laser.hits_player(&player));
+
+    laser.update(1.0);
+    assert!(laser.hits_player(&player));
+
+    let off_to_the_side = Laser { x: 500.0 + Player::WIDTH, ..laser.clone() };
+    assert!(!off_to_the_side.hits_player(&player));
+
+    laser.update(0.5);
+    assert!(!laser.hits_player(&player));
+}
+
+#[test]
+fn laser_enemy_fires_after_its_interval() {
+    let mut enemy = test_enemy(Point2 { x: 100.0, y: 100.0 }).with_laser(2.0);
+
+    enemy.update_laser(1.5);
+    assert!(enemy.laser().is_none());
+
+    enemy.update_laser(0.5);
+    let laser = enemy.laser().unwrap();
+    assert_eq!(laser.phase(), LaserPhase::Telegraph);
+    assert_eq!(laser.x, 150.0);
+
+    enemy.update_laser(Laser::TELEGRAPH_TIME + Laser::ACTIVE_TIME);
+    assert!(enemy.laser().is_none());
+}
+
+#[test]
+fn shadow_is_offset_down_and_right() {
+    let enemy = test_enemy(Point2 { x: 100.0, y: 100.0 });
+    let shadow = enemy.shadow_pos();
+
+    assert!(shadow.x > 100.0);
+    assert!(shadow.y > 100.0);
+    assert_eq!(shadow.x, 100.0 + Enemy::SHADOW_OFFSET.x);
+    assert_eq!(shadow.y, 100.0 + Enemy::SHADOW_OFFSET.y);
+}
+
+#[test]
+fn sustained_fire_overheats_until_cooled_below_reset() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+    let play_area = graphics::Rect::new(0.0, 0.0, 1000.0, 1000.0);
+    let still = Vector2 { x: 0.0, y: 0.0 };
+    player.time_until_next_shot = 0.0;
+
+    // Bypasses the cooldown so only the heat limits firing
+    let mut shots = 0;
+    while !player.shoot().is_empty() {
+        player.time_until_next_shot = 0.0;
+        shots += 1;
+    }
+    assert_eq!(shots, (Player::MAX_HEAT / Player::HEAT_PER_SHOT) as u32);
+    assert!(player.overheated);
+
+    // Still too hot just above the reset threshold
+    let cool_time = (Player::MAX_HEAT - Player::OVERHEAT_RESET) / Player::COOL_RATE;
+    player.update(still, cool_time * 0.9, play_area);
+    assert!(player.overheated);
+    assert!(player.shoot().is_empty());
+
+    player.update(still, cool_time * 0.2, play_area);
+    assert!(!player.overheated);
+    assert!(!player.shoot().is_empty());
+}
+
+#[test]
+fn player_draws_its_skins_image_for_its_state() {
+    let skins: std::collections::HashMap<Skin, SkinImages<&str>> = [
+        (Skin::Ferris, SkinImages { normal: "ferris normal", shooting: "ferris shooting" }),
+        (Skin::Golden, SkinImages { normal: "golden normal", shooting: "golden shooting" }),
+    ].into_iter().collect();
+
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 }).with_skin(Skin::Golden);
+    assert_eq!(*player.current_image(skin_images(&skins, player.skin)), "golden normal");
+
+    player.show_shooting();
+    assert_eq!(*player.current_image(skin_images(&skins, player.skin)), "golden shooting");
+
+    // Ghost didn't load, so it falls back to the default skin
+    player.skin = Skin::Ghost;
+    assert_eq!(*player.current_image(skin_images(&skins, player.skin)), "ferris shooting");
+}
+
+#[test]
+fn health_bar_fill_follows_health() {
+    let mock_sprite = Box::new(MockSprite { width: 100.0, height: 50.0 });
+    let mut enemy = Enemy::new("test", Point2 { x: 0.0, y: 0.0 }, 10.0, 4, 1, mock_sprite).unwrap();
+    assert_eq!(enemy.max_health(), 4);
+    assert_eq!(enemy.health_bar_fill_width(), 100.0);
+
+    enemy.hit(2);
+    assert_eq!(enemy.health_bar_fill_width(), 50.0);
+
+    enemy.hit(1);
+    assert_eq!(enemy.health_bar_fill_width(), 25.0);
+    assert!(enemy.health_bar_fill_width() > 0.0);
+}
+
+fn shots_while_holding_fire(player: &mut Player, seconds: f32) -> usize {
+    let play_area = graphics::Rect::new(0.0, 0.0, 1000.0, 1000.0);
+    let tick = 0.125;
+    let mut shots = 0;
+    for _ in 0..(seconds / tick) as usize {
+        player.update(Vector2 { x: 0.0, y: 0.0 }, tick, play_area);
+        shots += player.update_weapon(true, tick).len();
+    }
+    shots
+}
+
+#[test]
+fn autofire_shoots_once_per_cooldown_while_held() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 }).with_autofire(true);
+
+    assert_eq!(shots_while_holding_fire(&mut player, Player::SHOT_TIMEOUT * 4.0), 4);
+
+    // Letting go doesn't fire an extra shot
+    assert!(player.update_weapon(false, 0.0).is_empty());
+}
+
+#[test]
+fn without_autofire_holding_fires_once_per_press() {
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 });
+
+    assert_eq!(shots_while_holding_fire(&mut player, Player::SHOT_TIMEOUT * 4.0), 0);
+    assert_eq!(player.update_weapon(false, 0.0).len(), 1);
+}
+
+#[test]
+fn slowed_enemy_moves_slower_until_slow_wears_off() {
+    let mut enemy = test_enemy(Point2 { x: 100.0, y: 0.0 });
+    enemy.slow(0.5, 1.0);
+    assert!(enemy.is_slowed());
+
+    enemy.update(1.0);
+    assert_eq!(enemy.pos.y, 5.0);
+    assert!(!enemy.is_slowed());
+
+    enemy.update(1.0);
+    assert_eq!(enemy.pos.y, 15.0);
+}
+
+#[test]
+fn freeze_powerup_is_collected() {
+    let mut player = Player::new(Point2 { x: 400.0, y: 600.0 });
+    let mut powerup = Powerup::new(Point2 { x: 390.0, y: 540.0 }, PowerupKind::Freeze);
+
+    assert!(powerup.collect(&mut player));
+    assert!(!powerup.is_alive);
+}
@@ -0,0 +1,58 @@
+use ggez::GameResult;
+use ggez::mint::Point2;
+
+use shooter::entities::Enemy;
+use shooter::formation::*;
+use shooter::movement::SineWave;
+
+mod common;
+use common::MockSprite;
+
+fn make_enemy(pos: Point2<f32>) -> GameResult<Enemy> {
+    Enemy::new("test", pos, 100.0, 1, 1, Box::new(MockSprite::default()))
+}
+
+#[test]
+fn row_of_five_is_evenly_spaced() {
+    let screen_width = 1200.0;
+    let formation = Formation::row(5, 200.0, Point2 { x: 150.0, y: 0.0 });
+
+    let enemies = formation.build(make_enemy).unwrap();
+    assert_eq!(enemies.len(), 5);
+
+    let xs: Vec<f32> = enemies.iter().map(|enemy| enemy.pos.x).collect();
+    assert_eq!(xs, vec![150.0, 350.0, 550.0, 750.0, 950.0]);
+    for enemy in &enemies {
+        assert_eq!(enemy.pos.y, 0.0);
+        assert!(enemy.pos.x >= 0.0 && enemy.pos.x + 100.0 <= screen_width);
+    }
+}
+
+#[test]
+fn formation_moves_together() {
+    let formation = Formation::row(3, 200.0, Point2 { x: 0.0, y: 0.0 }).
+        with_movement(SineWave { amplitude: 50.0, period: 2.0 });
+    let mut enemies = formation.build(make_enemy).unwrap();
+
+    for _ in 0..30 {
+        for enemy in enemies.iter_mut() {
+            enemy.update(1.0 / 60.0);
+        }
+    }
+
+    for (i, enemy) in enemies.iter().enumerate() {
+        assert!((enemy.pos.x - enemies[0].pos.x - i as f32 * 200.0).abs() < 0.001);
+        assert_eq!(enemy.pos.y, enemies[0].pos.y);
+    }
+}
+
+#[test]
+fn v_shape_trails_behind_leader() {
+    let formation = Formation::new(FormationPattern::VShape { count: 5, spacing: 50.0 }, Point2 { x: 500.0, y: 0.0 });
+    let positions = formation.positions();
+
+    assert_eq!((positions[0].x, positions[0].y), (500.0, 0.0));
+    assert_eq!((positions[1].x, positions[1].y), (550.0, -50.0));
+    assert_eq!((positions[2].x, positions[2].y), (450.0, -50.0));
+    assert_eq!((positions[4].x, positions[4].y), (400.0, -100.0));
+}
@@ -0,0 +1,34 @@
+use shooter::highscores::HighScores;
+
+#[test]
+fn insert_keeps_scores_sorted_descending() {
+    let mut high_scores = HighScores::default();
+
+    high_scores.insert("a", 10);
+    high_scores.insert("b", 30);
+    high_scores.insert("c", 20);
+
+    let scores: Vec<u32> = high_scores.entries.iter().map(|entry| entry.score).collect();
+    assert_eq!(scores, vec![30, 20, 10]);
+    assert_eq!(high_scores.best(), 30);
+}
+
+#[test]
+fn insert_truncates_to_top_ten() {
+    let mut high_scores = HighScores::default();
+    for score in 1..=10 {
+        assert!(high_scores.insert("player", score * 10));
+    }
+
+    assert!(!high_scores.insert("low", 5));
+    assert!(high_scores.insert("high", 55));
+
+    assert_eq!(high_scores.entries.len(), HighScores::MAX_ENTRIES);
+    assert_eq!(high_scores.entries.last().unwrap().score, 20);
+    assert!(high_scores.entries.iter().any(|entry| entry.name == "high"));
+}
+
+#[test]
+fn empty_table_has_zero_best() {
+    assert_eq!(HighScores::default().best(), 0);
+}
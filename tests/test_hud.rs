@@ -0,0 +1,59 @@
+use ggez::mint::Point2;
+
+use shooter::entities::Player;
+use shooter::hud::*;
+
+#[test]
+fn health_bar_is_proportional_to_health() {
+    let mut player = Player::new(Point2 { x: 0.0, y: 0.0 });
+    assert_eq!(health_bar_width(&player), HEALTH_BAR_WIDTH);
+
+    player.health = 1;
+    let expected = HEALTH_BAR_WIDTH / Player::MAX_HEALTH as f32;
+    assert!((health_bar_width(&player) - expected).abs() < 0.001);
+
+    player.health = 0;
+    assert_eq!(health_bar_width(&player), 0.0);
+}
+
+#[test]
+fn fps_label_is_rounded() {
+    assert_eq!(fps_label(59.6), "FPS: 60");
+    assert_eq!(fps_label(30.2), "FPS: 30");
+}
+
+#[test]
+fn ammo_label_shows_reloading() {
+    let mut player = Player::new(Point2 { x: 0.0, y: 0.0 });
+    assert_eq!(ammo_label(&player), format!("Ammo: {}/{}", Player::MAG_SIZE, Player::MAG_SIZE));
+
+    player.reload_timer = Player::RELOAD_TIME;
+    assert_eq!(ammo_label(&player), "Reloading...");
+}
+
+#[test]
+fn heat_bar_fills_with_heat() {
+    let mut player = Player::new(Point2 { x: 0.0, y: 0.0 });
+    assert_eq!(heat_bar_width(&player), 0.0);
+
+    player.heat = Player::MAX_HEAT / 2.0;
+    assert!((heat_bar_width(&player) - HEALTH_BAR_WIDTH / 2.0).abs() < 0.001);
+}
+
+#[test]
+fn score_popup_drifts_up_and_fades_out() {
+    let mut popup = ScorePopup::new(Point2 { x: 100.0, y: 200.0 }, 30);
+    assert_eq!(popup.alpha(), 1.0);
+    assert_eq!(ScorePopup::label(30), "+30");
+
+    popup.update(ScorePopup::LIFETIME / 2.0);
+    assert!((popup.alpha() - 0.5).abs() < 0.001);
+    assert!((popup.pos.y - (200.0 - ScorePopup::DRIFT_SPEED * ScorePopup::LIFETIME / 2.0)).abs() < 0.001);
+    assert_eq!(popup.pos.x, 100.0);
+    assert!(popup.is_alive());
+
+    popup.update(ScorePopup::LIFETIME / 2.0);
+    assert!((popup.pos.y - (200.0 - ScorePopup::DRIFT_SPEED * ScorePopup::LIFETIME)).abs() < 0.001);
+    assert!(popup.alpha() < 0.001);
+    assert!(!popup.is_alive());
+}
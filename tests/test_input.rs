@@ -0,0 +1,77 @@
+use ggez::input::keyboard::KeyCode;
+use shooter::input::*;
+
+#[test]
+fn deadzone_ignores_tiny_inputs() {
+    assert_eq!(apply_deadzone(0.0), 0.0);
+    assert_eq!(apply_deadzone(0.05), 0.0);
+    assert_eq!(apply_deadzone(-GAMEPAD_DEADZONE / 2.0), 0.0);
+}
+
+#[test]
+fn deadzone_passes_full_deflection() {
+    assert_eq!(apply_deadzone(1.0), 1.0);
+    assert_eq!(apply_deadzone(-1.0), -1.0);
+}
+
+#[test]
+fn deadzone_normalizes_out_of_range_values() {
+    assert_eq!(apply_deadzone(1.5), 1.0);
+    assert_eq!(apply_deadzone(-3.0), -1.0);
+}
+
+#[test]
+fn deadzone_is_continuous_at_its_edge() {
+    assert!(apply_deadzone(GAMEPAD_DEADZONE + 0.001).abs() < 0.01);
+    assert!(apply_deadzone(0.5) > 0.0 && apply_deadzone(0.5) < 0.5);
+}
+
+#[test]
+fn default_bindings_match_original_keys() {
+    let config = InputConfig::default();
+
+    assert_eq!(config.action_for(KeyCode::Left), Some(Action::MoveLeft));
+    assert_eq!(config.action_for(KeyCode::Right), Some(Action::MoveRight));
+    assert_eq!(config.action_for(KeyCode::Space), Some(Action::Fire));
+    assert_eq!(config.action_for(KeyCode::Escape), Some(Action::Pause));
+    assert_eq!(config.action_for(KeyCode::Return), Some(Action::Confirm));
+    assert_eq!(config.action_for(KeyCode::W), None);
+}
+
+#[test]
+fn remapped_key_triggers_its_action() {
+    let config = InputConfig::parse("
+        # WASD for lefties
+        move_left = A
+        move_right = D
+        fire = LShift
+    ").unwrap();
+
+    assert_eq!(config.action_for(KeyCode::A), Some(Action::MoveLeft));
+    assert_eq!(config.action_for(KeyCode::D), Some(Action::MoveRight));
+    assert_eq!(config.action_for(KeyCode::LShift), Some(Action::Fire));
+    assert_eq!(config.action_for(KeyCode::Left), None);
+    assert_eq!(config.key_for(Action::Pause), KeyCode::Escape);
+}
+
+#[test]
+fn malformed_config_is_rejected() {
+    assert!(InputConfig::parse("fire Z").is_err());
+    assert!(InputConfig::parse("jump = Z").is_err());
+    assert!(InputConfig::parse("fire = NotAKey").is_err());
+}
+
+#[test]
+fn fps_toggle_is_bound_to_f3() {
+    assert_eq!(InputConfig::default().action_for(KeyCode::F3), Some(Action::ToggleFps));
+}
+
+#[test]
+fn hitbox_toggle_is_bound_to_f2() {
+    assert_eq!(InputConfig::default().action_for(KeyCode::F2), Some(Action::ToggleHitboxes));
+}
+
+#[test]
+fn mute_toggle_is_bound_to_m() {
+    assert_eq!(InputConfig::default().action_for(KeyCode::M), Some(Action::ToggleMute));
+}
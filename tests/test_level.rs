@@ -0,0 +1,59 @@
+use shooter::level::*;
+
+const LEVEL: &str = "
+    # A short test level
+    2.0, 500, Data Races, 150
+    1.0, 100, Segfaults, 100   # out of order on purpose
+    1.0, 900, Segfaults, 100
+    3.5, 300, Unnecessary Heap\\nAllocations, 80
+";
+
+#[test]
+fn level_parses_and_sorts_events() {
+    let level = Level::parse(LEVEL).unwrap();
+    let times: Vec<f32> = level.events().iter().map(|event| event.time).collect();
+
+    assert_eq!(times, vec![1.0, 1.0, 2.0, 3.5]);
+    assert_eq!(level.events()[0].spawn.pos.x, 100.0);
+    assert_eq!(level.events()[1].spawn.pos.x, 900.0);
+    assert_eq!(level.events()[3].spawn.label, "Unnecessary Heap\nAllocations");
+}
+
+#[test]
+fn events_fire_once_at_their_time() {
+    let mut level = Level::parse(LEVEL).unwrap();
+
+    assert!(level.due_spawns(0.5).is_empty());
+    assert_eq!(level.due_spawns(1.0).len(), 2);
+    assert!(level.due_spawns(1.5).is_empty());
+
+    let spawns = level.due_spawns(2.0);
+    assert_eq!(spawns.len(), 1);
+    assert_eq!(spawns[0].label, "Data Races");
+    assert_eq!(spawns[0].speed, 150.0);
+
+    assert_eq!(level.due_spawns(10.0).len(), 1);
+    assert!(level.is_finished());
+    assert!(level.due_spawns(20.0).is_empty());
+}
+
+#[test]
+fn skip_to_drops_earlier_events() {
+    let mut level = Level::parse(LEVEL).unwrap();
+
+    level.skip_to(2.0);
+    assert_eq!(level.due_spawns(2.0).len(), 1);
+
+    level.restart();
+    assert_eq!(level.due_spawns(2.0).len(), 3);
+}
+
+#[test]
+fn malformed_lines_are_reported() {
+    let error = Level::parse("1.0, 100, Segfaults, 100\n1.0, 100, Segfaults").unwrap_err();
+    assert!(error.starts_with("line 2:"));
+
+    assert!(Level::parse("soon, 100, Segfaults, 100").is_err());
+    assert!(Level::parse("1.0, 100, Gremlins, 100").is_err());
+    assert!(Level::parse("1.0, 100, Segfaults, -5").is_err());
+}
@@ -0,0 +1,48 @@
+use ggez::mint::Point2;
+
+use shooter::movement::*;
+
+#[test]
+fn sine_wave_peaks_at_quarter_period() {
+    let pattern = SineWave { amplitude: 50.0, period: 2.0 };
+
+    assert!((pattern.offset(0.5).x - 50.0).abs() < 0.001);
+    assert!(pattern.offset(1.0).x.abs() < 0.001);
+    assert!((pattern.offset(1.5).x + 50.0).abs() < 0.001);
+}
+
+#[test]
+fn zigzag_matches_sine_wave_extremes() {
+    let pattern = Zigzag { amplitude: 50.0, period: 2.0 };
+
+    assert!(pattern.offset(0.0).x.abs() < 0.001);
+    assert!((pattern.offset(0.5).x - 50.0).abs() < 0.001);
+    assert!(pattern.offset(1.0).x.abs() < 0.001);
+    assert!((pattern.offset(1.5).x + 50.0).abs() < 0.001);
+}
+
+#[test]
+fn straight_down_has_no_offset() {
+    let offset = StraightDown.offset(10.0);
+
+    assert_eq!(offset.x, 0.0);
+    assert_eq!(offset.y, 0.0);
+}
+
+#[test]
+fn seek_player_drifts_toward_target_with_capped_speed() {
+    let mut pattern = SeekPlayer::new(80.0, 60.0);
+    let target = Point2 { x: 500.0, y: 900.0 };
+
+    let mut x = 100.0;
+    let mut last_drift = 0.0;
+    for _ in 0..300 {
+        let drift = pattern.steer(Point2 { x, y: 0.0 }, target, 1.0 / 60.0).x;
+        assert!(drift >= last_drift && drift <= 80.0);
+        x += drift / 60.0;
+        last_drift = drift;
+    }
+
+    assert!(x > 100.0 && x < 500.0);
+    assert_eq!(last_drift, 80.0);
+}
@@ -0,0 +1,82 @@
+use ggez::graphics;
+use ggez::mint::{Point2, Vector2};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use shooter::particles::*;
+
+#[test]
+fn particle_expires_after_lifetime() {
+    let mut particle = Particle::new(
+        Point2 { x: 0.0, y: 0.0 }, Vector2 { x: 10.0, y: 0.0 },
+        0.5, graphics::Color::WHITE);
+
+    particle.update(0.25);
+    assert!(particle.is_alive());
+    assert!((particle.alpha() - 0.5).abs() < 0.001);
+
+    particle.update(0.25);
+    assert!(!particle.is_alive());
+    assert_eq!(particle.alpha(), 0.0);
+}
+
+#[test]
+fn explosion_spawns_configured_particle_count() {
+    let explosion = Explosion { particle_count: 12, ..Explosion::default() };
+    let mut rng = StdRng::seed_from_u64(1);
+
+    let mut particles = explosion.burst(Point2 { x: 50.0, y: 50.0 }, &mut rng);
+    assert_eq!(particles.len(), 12);
+
+    for particle in particles.iter_mut() {
+        particle.update(explosion.lifetime);
+    }
+    assert!(particles.iter().all(|particle| !particle.is_alive()));
+}
+
+#[test]
+fn muzzle_flash_fades_out_and_expires_after_lifetime() {
+    let mut flash = MuzzleFlash::new(Point2 { x: 0.0, y: 0.0 });
+    assert!(flash.is_alive());
+    assert_eq!(flash.alpha(), 1.0);
+    assert_eq!(flash.size(), MuzzleFlash::START_SIZE);
+
+    flash.update(MuzzleFlash::LIFETIME / 2.0);
+    assert!(flash.is_alive());
+    assert!(flash.alpha() > 0.0 && flash.alpha() < 1.0);
+    assert!(flash.size() > MuzzleFlash::START_SIZE);
+
+    flash.update(MuzzleFlash::LIFETIME / 2.0);
+    assert!(!flash.is_alive());
+    assert_eq!(flash.alpha(), 0.0);
+    assert_eq!(flash.size(), MuzzleFlash::END_SIZE);
+}
+
+#[test]
+fn particle_system_drops_emissions_past_capacity() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut system = ParticleSystem::new(50);
+
+    assert_eq!(system.emit_burst(Point2 { x: 0.0, y: 0.0 }, 30, &mut rng), 30);
+    assert_eq!(system.emit_burst(Point2 { x: 0.0, y: 0.0 }, 30, &mut rng), 20);
+    assert_eq!(system.live_count(), 50);
+    assert_eq!(system.capacity(), 50);
+
+    let extra = Particle::new(Point2 { x: 0.0, y: 0.0 }, Vector2 { x: 0.0, y: 0.0 }, 1.0, graphics::Color::WHITE);
+    assert!(!system.emit(extra));
+    assert_eq!(system.capacity(), 50);
+}
+
+#[test]
+fn expired_particles_free_their_slots() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let explosion = Explosion { particle_count: 10, lifetime: 0.5, ..Explosion::default() };
+    let mut system = ParticleSystem::new(10).with_explosion(explosion);
+
+    assert_eq!(system.explode(Point2 { x: 0.0, y: 0.0 }, &mut rng), 10);
+    assert_eq!(system.explode(Point2 { x: 0.0, y: 0.0 }, &mut rng), 0);
+
+    system.update(0.5);
+    assert_eq!(system.live_count(), 0);
+    assert_eq!(system.explode(Point2 { x: 0.0, y: 0.0 }, &mut rng), 10);
+}
@@ -0,0 +1,37 @@
+use ggez::mint::{Point2, Vector2};
+
+use shooter::pool::*;
+
+fn up() -> Vector2<f32> {
+    Vector2 { x: 0.0, y: -500.0 }
+}
+
+#[test]
+fn spawning_into_full_pool_does_nothing() {
+    let mut pool = ShotPool::new(2);
+    assert_eq!(pool.live_count(), 0);
+
+    assert!(pool.spawn(Point2 { x: 0.0, y: 100.0 }, up()).is_some());
+    assert!(pool.spawn(Point2 { x: 10.0, y: 100.0 }, up()).is_some());
+    assert!(pool.spawn(Point2 { x: 20.0, y: 100.0 }, up()).is_none());
+
+    assert_eq!(pool.live_count(), 2);
+    assert!(pool.iter().all(|shot| shot.pos.x != 20.0));
+}
+
+#[test]
+fn despawned_slot_is_reused() {
+    let mut pool = ShotPool::new(2);
+    pool.spawn(Point2 { x: 0.0, y: 100.0 }, up());
+    let leaving = pool.spawn(Point2 { x: 10.0, y: 0.0 }, up()).unwrap();
+
+    // Only the second shot gets far enough to leave the top of the screen
+    pool.update_all(0.1);
+    assert_eq!(pool.live_count(), 1);
+    assert!(pool.get(leaving).is_none());
+
+    let reused = pool.spawn(Point2 { x: 20.0, y: 100.0 }, up()).unwrap();
+    assert_eq!(reused, leaving);
+    assert_eq!(pool.get(reused).unwrap().pos.x, 20.0);
+    assert_eq!(pool.live_count(), 2);
+}
@@ -0,0 +1,54 @@
+use ggez::graphics;
+use ggez::mint::{Point2, Vector2};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use shooter::entities::Player;
+use shooter::replay::*;
+
+fn play(player: &mut Player, frame: FrameInput) -> Point2<f32> {
+    let play_area = graphics::Rect::new(0.0, 0.0, 1200.0, 1000.0);
+    player.update(frame.move_amount(), 1.0 / 60.0, play_area);
+    player.update_weapon(frame.fire, 1.0 / 60.0);
+    player.pos
+}
+
+#[test]
+fn replaying_a_recording_reproduces_player_positions() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut recorder = Recorder::new(5);
+    let mut player = Player::new(Point2 { x: 600.0, y: 1000.0 });
+
+    let mut recorded_positions = Vec::new();
+    for _ in 0..300 {
+        let move_amount = Vector2 { x: rng.gen_range(-1.0 .. 1.0), y: rng.gen_range(-1.0 .. 1.0) };
+        let frame = FrameInput::new(move_amount, rng.gen_bool(0.3), false);
+        recorder.record(frame);
+        recorded_positions.push(play(&mut player, frame));
+    }
+
+    let json = recorder.finish().to_json().unwrap();
+    let mut replayer = Replayer::new(Replay::from_json(&json).unwrap());
+    assert_eq!(replayer.seed(), 5);
+
+    let mut player = Player::new(Point2 { x: 600.0, y: 1000.0 });
+    let mut replayed_positions = Vec::new();
+    while let Some(frame) = replayer.next_frame() {
+        replayed_positions.push(play(&mut player, frame));
+    }
+
+    assert_eq!(replayed_positions, recorded_positions);
+}
+
+#[test]
+fn replay_stops_at_the_end_of_the_recording() {
+    let mut recorder = Recorder::new(0);
+    recorder.record(FrameInput::default());
+    let mut replayer = Replayer::new(recorder.finish());
+
+    assert!(!replayer.is_finished());
+    assert_eq!(replayer.next_frame(), Some(FrameInput::default()));
+    assert!(replayer.is_finished());
+    assert_eq!(replayer.next_frame(), None);
+    assert_eq!(replayer.next_frame(), None);
+}
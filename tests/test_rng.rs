@@ -0,0 +1,38 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use shooter::difficulty::Difficulty;
+use shooter::rng;
+use shooter::spawner::{Spawn, Spawner};
+
+// Mirrors how a game hands out generators: one seed, forked per system
+fn game_spawns(seed: u64) -> Vec<Spawn> {
+    let mut game_rng = StdRng::seed_from_u64(seed);
+    let mut spawner = Spawner::new(1200.0, rng::fork(&mut game_rng));
+    let mut difficulty = Difficulty::new();
+
+    (0..60 * 60).flat_map(|_| {
+        difficulty.update(1.0 / 60.0);
+        spawner.next_spawns(1.0 / 60.0, &difficulty)
+    }).collect()
+}
+
+#[test]
+fn same_seed_gives_same_spawns() {
+    let spawns = game_spawns(1234);
+
+    assert!(!spawns.is_empty());
+    assert_eq!(spawns, game_spawns(1234));
+    assert_ne!(spawns, game_spawns(4321));
+}
+
+#[test]
+fn forks_are_deterministic_and_independent() {
+    let mut a = StdRng::seed_from_u64(5);
+    let mut b = StdRng::seed_from_u64(5);
+
+    let first: u64 = rng::fork(&mut a).gen();
+    let second: u64 = rng::fork(&mut a).gen();
+    assert_eq!(first, rng::fork(&mut b).gen::<u64>());
+    assert_ne!(first, second);
+}
@@ -0,0 +1,109 @@
+use shooter::score::{Combo, Score, WaveTracker};
+
+#[test]
+fn adding_past_high_score_bumps_it() {
+    let mut score = Score { current: 0, high: 10 };
+
+    score.add(5);
+    assert_eq!(score.high, 10);
+
+    score.add(7);
+    assert_eq!(score.current, 12);
+    assert_eq!(score.high, 12);
+}
+
+#[test]
+fn reset_keeps_high_score() {
+    let mut score = Score::new();
+    score.add(20);
+
+    score.reset();
+    assert_eq!(score.current, 0);
+    assert_eq!(score.high, 20);
+}
+
+#[test]
+fn quick_kills_raise_combo() {
+    let mut combo = Combo::new();
+
+    assert_eq!(combo.register_kill(), 1);
+    combo.decay(Combo::COMBO_WINDOW / 2.0);
+    assert_eq!(combo.register_kill(), 2);
+    combo.decay(Combo::COMBO_WINDOW / 2.0);
+    assert_eq!(combo.register_kill(), 3);
+}
+
+#[test]
+fn combo_resets_after_window() {
+    let mut combo = Combo::new();
+    combo.register_kill();
+    combo.register_kill();
+    assert_eq!(combo.multiplier(), 2);
+
+    combo.decay(Combo::COMBO_WINDOW + 0.1);
+    assert_eq!(combo.multiplier(), 1);
+    assert_eq!(combo.register_kill(), 1);
+}
+
+#[test]
+fn combo_is_capped() {
+    let mut combo = Combo::new();
+    for _ in 0..20 {
+        combo.register_kill();
+    }
+    assert_eq!(combo.multiplier(), Combo::MAX_MULTIPLIER);
+}
+
+#[test]
+fn killing_whole_wave_earns_bonus() {
+    let mut waves = WaveTracker::new();
+    waves.record_spawn(1);
+    waves.record_spawn(2);
+    waves.record_kill(1);
+    waves.record_kill(2);
+
+    assert_eq!(waves.update(WaveTracker::WAVE_LENGTH / 2.0), None);
+    assert_eq!(waves.update(WaveTracker::WAVE_LENGTH / 2.0), Some(WaveTracker::PERFECT_CLEAR_BONUS));
+}
+
+#[test]
+fn escaped_enemy_loses_bonus() {
+    let mut waves = WaveTracker::new();
+    waves.record_spawn(1);
+    waves.record_spawn(2);
+    waves.record_kill(1);
+    waves.record_escape(2);
+
+    assert_eq!(waves.update(WaveTracker::WAVE_LENGTH), None);
+}
+
+#[test]
+fn escaped_shard_loses_bonus() {
+    let mut waves = WaveTracker::new();
+    waves.record_spawn(1);
+    waves.record_kill(1);
+    // The shards of enemy 1
+    waves.record_spawn(2);
+    waves.record_spawn(3);
+    waves.record_kill(2);
+    waves.record_escape(3);
+
+    assert_eq!(waves.update(WaveTracker::WAVE_LENGTH), None);
+}
+
+#[test]
+fn survivors_and_empty_waves_earn_nothing() {
+    let mut waves = WaveTracker::new();
+    waves.record_spawn(1);
+    waves.record_spawn(2);
+    waves.record_kill(1);
+    assert_eq!(waves.update(WaveTracker::WAVE_LENGTH), None);
+
+    // Enemy 2 came from the last wave, so it doesn't spoil this one
+    waves.record_spawn(3);
+    waves.record_escape(2);
+    waves.record_kill(3);
+    assert_eq!(waves.update(WaveTracker::WAVE_LENGTH), Some(WaveTracker::PERFECT_CLEAR_BONUS));
+
+    assert_eq!(waves.update(WaveTracker::WAVE_LENGTH), None);
+}
@@ -0,0 +1,17 @@
+use ggez::mint::{Point2, Vector2};
+
+use shooter::entities::Player;
+use shooter::screen::*;
+
+#[test]
+fn clamp_bounds_track_screen_width() {
+    for width in [800.0, 1200.0, 1920.0] {
+        let screen = ScreenConfig::new(width, 1000.0);
+        let mut player = Player::new(Point2 { x: width / 2.0, y: 1000.0 });
+
+        for _ in 0..300 {
+            player.update(Vector2 { x: 1.0, y: 0.0 }, 1.0 / 60.0, Player::play_area(&screen));
+        }
+        assert_eq!(player.pos.x, width);
+    }
+}
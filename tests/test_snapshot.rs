@@ -0,0 +1,81 @@
+use ggez::mint::{Point2, Vector2};
+
+use shooter::entities::{Enemy, Player, Shot};
+use shooter::snapshot::*;
+
+mod common;
+use common::MockSprite;
+
+fn snapshot() -> GameSnapshot {
+    let mut player = Player::new(Point2 { x: 600.0, y: 1000.0 });
+    player.pos = Point2 { x: 420.0, y: 900.0 };
+    player.health = 2;
+
+    let enemies = [
+        Enemy::new("Segfaults", Point2 { x: 10.0, y: 20.0 }, 100.0, 1, 1, Box::new(MockSprite::default())).unwrap(),
+        Enemy::new("Bloat", Point2 { x: 300.0, y: 40.0 }, 50.0, 2, 5, Box::new(MockSprite::default())).unwrap().
+            with_fire_interval(2.0),
+        Enemy::new("Sidecar", Point2 { x: 0.0, y: 100.0 }, 50.0, 1, 1, Box::new(MockSprite::default())).unwrap().
+            with_velocity(Vector2 { x: 40.0, y: 30.0 }),
+    ];
+    let shots = [Shot::new(Point2 { x: 400.0, y: 500.0 }, Shot::direction_velocity(0.0)).with_power(2, 1.5)];
+    let enemy_shots = [Shot::new(Point2 { x: 50.0, y: 60.0 }, Vector2 { x: 0.0, y: Shot::ENEMY_SPEED })];
+
+    GameSnapshot {
+        player: PlayerSnapshot::capture(&player),
+        enemies: enemies.iter().map(EnemySnapshot::capture).collect(),
+        shots: shots.iter().map(ShotSnapshot::capture).collect(),
+        enemy_shots: enemy_shots.iter().map(ShotSnapshot::capture).collect(),
+        score: 1234,
+        elapsed: 42.5,
+    }
+}
+
+#[test]
+fn snapshot_round_trips_through_json() {
+    let snapshot = snapshot();
+    let loaded = GameSnapshot::from_json(&snapshot.to_json().unwrap()).unwrap();
+
+    assert_eq!(loaded, snapshot);
+    assert_eq!(loaded.enemies.len(), 3);
+    assert_eq!((loaded.enemies[1].x, loaded.enemies[1].y), (300.0, 40.0));
+    assert_eq!(loaded.enemies[1].label, "Bloat");
+    assert_eq!(loaded.enemies[1].fire_interval, Some(2.0));
+    assert_eq!((loaded.player.x, loaded.player.y), (420.0, 900.0));
+}
+
+#[test]
+fn restored_entities_match_snapshot() {
+    let snapshot = snapshot();
+
+    let mut player = Player::new(Point2 { x: 0.0, y: 0.0 });
+    snapshot.player.restore(&mut player);
+    assert_eq!((player.pos.x, player.pos.y), (420.0, 900.0));
+    assert_eq!(player.health, 2);
+
+    let shot = snapshot.shots[0].clone().into_shot();
+    assert_eq!((shot.pos.x, shot.pos.y), (400.0, 500.0));
+    assert_eq!(shot.velocity().y, -Shot::SPEED);
+    assert_eq!((shot.damage, shot.scale), (2, 1.5));
+}
+
+#[test]
+fn restored_enemy_keeps_sideways_velocity() {
+    let snapshot = snapshot();
+
+    let enemy = snapshot.enemies[2].clone().into_enemy_with_sprite(Box::new(MockSprite::default())).unwrap();
+    assert_eq!(enemy.base_velocity(), Vector2 { x: 40.0, y: 30.0 });
+}
+
+#[test]
+fn old_saves_load_speed_as_downward_velocity() {
+    let json = r#"{"label":"Bloat","x":1.0,"y":2.0,"speed":50.0,"health":2,"point_value":5,"fire_interval":null}"#;
+    let enemy: EnemySnapshot = serde_json::from_str(json).unwrap();
+
+    assert_eq!((enemy.velocity_x, enemy.velocity_y), (0.0, 50.0));
+}
+
+#[test]
+fn garbage_save_is_rejected() {
+    assert!(GameSnapshot::from_json("not a savegame").is_err());
+}
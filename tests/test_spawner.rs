@@ -0,0 +1,134 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use shooter::difficulty::Difficulty;
+use shooter::entities::EnemyKind;
+use shooter::spawner::{Spawn, SpawnEdge, SpawnWarning, Spawner};
+use shooter::words::WordList;
+
+fn spawns_over(seconds: f32, seed: u64) -> Vec<Spawn> {
+    let mut spawner = Spawner::new(1200.0, StdRng::seed_from_u64(seed));
+    let difficulty = Difficulty::new();
+
+    let ticks = (seconds * 60.0) as u32;
+    (0..ticks).flat_map(|_| spawner.next_spawns(1.0 / 60.0, &difficulty)).collect()
+}
+
+#[test]
+fn spawner_is_deterministic_for_a_seed() {
+    let spawns = spawns_over(60.0, 42);
+
+    assert_eq!(spawns, spawns_over(60.0, 42));
+    assert_ne!(spawns, spawns_over(60.0, 43));
+}
+
+#[test]
+fn spawner_emits_expected_number_of_enemies() {
+    let spawns = spawns_over(60.0, 42);
+    let xs: Vec<f32> = spawns.iter().map(|spawn| spawn.pos.x).collect();
+
+    // Pinned to the output of this seed, so any change to how the spawner draws from the rng
+    // shows up here
+    assert_eq!(xs, vec![
+        146.75072, 934.17664, 700.5111, 64.344154, 215.11533, 73.40591, 893.105, 421.5056,
+        816.7409, 65.277016, 879.1228, 753.01636, 282.12482, 871.72534, 712.57697, 971.10333,
+        568.8838, 102.81396, 422.26526, 346.72546, 306.22644, 8.4864855, 275.20102, 77.39332,
+        371.6541, 83.88229, 16.76526, 1080.7083, 313.9934,
+    ]);
+}
+
+#[test]
+fn spawner_keeps_enemies_on_screen() {
+    for spawn in spawns_over(60.0, 7) {
+        assert!(spawn.pos.x >= 0.0);
+        assert!(spawn.pos.x <= 1200.0 - Spawner::SPAWN_MARGIN);
+        assert_eq!(spawn.pos.y, 0.0);
+    }
+}
+
+#[test]
+fn spawner_waits_before_first_enemy() {
+    let mut spawner = Spawner::new(1200.0, StdRng::seed_from_u64(1));
+    let difficulty = Difficulty::new();
+
+    assert!(spawner.next_spawns(Spawner::FIRST_SPAWN_DELAY / 2.0, &difficulty).is_empty());
+    assert_eq!(spawner.next_spawns(Spawner::FIRST_SPAWN_DELAY / 2.0, &difficulty).len(), 1);
+}
+
+#[test]
+fn spawner_labels_come_from_its_word_list() {
+    let words = WordList::parse("foo\nbar");
+    let mut spawner = Spawner::new(1200.0, StdRng::seed_from_u64(3)).with_words(words);
+    let difficulty = Difficulty::new();
+
+    let spawns: Vec<Spawn> = (0..600).flat_map(|_| spawner.next_spawns(1.0 / 60.0, &difficulty)).collect();
+    assert!(!spawns.is_empty());
+    assert!(spawns.iter().all(|spawn| spawn.label == "foo" || spawn.label == "bar"));
+}
+
+#[test]
+fn fast_enemies_appear_where_they_were_warned() {
+    let mut spawner = Spawner::new(1200.0, StdRng::seed_from_u64(42)).with_warning_lead_time(0.5);
+    let difficulty = Difficulty::new();
+
+    let mut warned: Option<SpawnWarning> = None;
+    let mut waited = 0.0;
+    for _ in 0..60 * 120 {
+        let spawns = spawner.next_spawns(1.0 / 60.0, &difficulty);
+
+        match &warned {
+            None => warned = spawner.warnings().first().cloned(),
+            Some(warning) => {
+                waited += 1.0 / 60.0;
+                if let Some(spawn) = spawns.iter().find(|spawn| spawn.pos == warning.pos()) {
+                    assert_eq!(spawn.kind, EnemyKind::Fast);
+                    assert!((waited - warning.timer).abs() < 0.02);
+                    return;
+                }
+                assert!(spawner.warnings().iter().any(|other| other.pos() == warning.pos()));
+            },
+        }
+    }
+    panic!("no warned spawn came in");
+}
+
+#[test]
+fn zero_lead_time_spawns_fast_enemies_straight_away() {
+    let mut spawner = Spawner::new(1200.0, StdRng::seed_from_u64(42)).with_warning_lead_time(0.0);
+    let difficulty = Difficulty::new();
+
+    for _ in 0..60 * 60 {
+        spawner.next_spawns(1.0 / 60.0, &difficulty);
+        assert!(spawner.warnings().is_empty());
+    }
+}
+
+#[test]
+fn side_spawns_start_on_their_edge_heading_inwards() {
+    let mut spawner = Spawner::new(1200.0, StdRng::seed_from_u64(5)).with_side_spawn_chance(1.0);
+    let difficulty = Difficulty::new();
+
+    let spawns: Vec<Spawn> = (0..3600).flat_map(|_| spawner.next_spawns(1.0 / 60.0, &difficulty)).collect();
+    assert!(spawns.iter().any(|spawn| spawn.edge == SpawnEdge::Left));
+    assert!(spawns.iter().any(|spawn| spawn.edge == SpawnEdge::Right));
+
+    for spawn in spawns {
+        match spawn.edge {
+            SpawnEdge::Left => assert_eq!(spawn.pos.x, 0.0),
+            SpawnEdge::Right => assert_eq!(spawn.pos.x, 1200.0 - Spawner::SPAWN_MARGIN),
+            SpawnEdge::Top => panic!("every spawn should come from the sides"),
+        }
+        assert!(spawn.pos.y >= 0.0 && spawn.pos.y < Spawner::SIDE_SPAWN_MAX_Y);
+    }
+}
+
+#[test]
+fn left_spawns_move_right_and_down() {
+    let velocity = SpawnEdge::Left.velocity(100.0);
+    assert!(velocity.x > 0.0);
+    assert!(velocity.y > 0.0);
+    assert!(((velocity.x * velocity.x + velocity.y * velocity.y).sqrt() - 100.0).abs() < 0.001);
+
+    assert!(SpawnEdge::Right.velocity(100.0).x < 0.0);
+    assert_eq!(SpawnEdge::Top.velocity(100.0).x, 0.0);
+}
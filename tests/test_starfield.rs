@@ -0,0 +1,38 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use shooter::starfield::*;
+
+#[test]
+fn same_seed_gives_same_layout() {
+    let a = Starfield::new(20, 800.0, 600.0, &mut StdRng::seed_from_u64(3));
+    let b = Starfield::new(20, 800.0, 600.0, &mut StdRng::seed_from_u64(3));
+
+    assert_eq!(a.stars.len(), 20);
+    for (a, b) in a.stars.iter().zip(b.stars.iter()) {
+        assert_eq!((a.pos.x, a.pos.y, a.depth), (b.pos.x, b.pos.y, b.depth));
+    }
+}
+
+#[test]
+fn nearer_stars_scroll_faster() {
+    let starfield = Starfield::new(50, 800.0, 600.0, &mut StdRng::seed_from_u64(3));
+
+    for star in &starfield.stars {
+        assert!(star.depth >= 1 && star.depth <= Starfield::LAYERS);
+        assert_eq!(star.speed, Starfield::BASE_SPEED * star.depth as f32);
+    }
+}
+
+#[test]
+fn star_past_bottom_wraps_to_top() {
+    let mut starfield = Starfield::new(1, 800.0, 600.0, &mut StdRng::seed_from_u64(3));
+    starfield.stars[0].pos.y = 599.0;
+    let speed = starfield.stars[0].speed;
+
+    starfield.update(0.1);
+
+    let y = starfield.stars[0].pos.y;
+    assert!(y >= 0.0);
+    assert!(y < speed * 0.1);
+}
@@ -0,0 +1,29 @@
+use shooter::input::Action;
+use shooter::state::GameState;
+
+#[test]
+fn enter_starts_game_from_menu() {
+    assert_eq!(GameState::Menu.handle_action(Action::Confirm), GameState::Playing);
+    assert_eq!(GameState::Menu.handle_action(Action::Fire), GameState::Menu);
+}
+
+#[test]
+fn escape_toggles_pause() {
+    assert_eq!(GameState::Playing.handle_action(Action::Pause), GameState::Paused);
+    assert_eq!(GameState::Paused.handle_action(Action::Pause), GameState::Playing);
+    assert_eq!(GameState::Paused.handle_action(Action::Confirm), GameState::Paused);
+}
+
+#[test]
+fn enter_returns_to_menu_after_game_over() {
+    assert_eq!(GameState::GameOver.handle_action(Action::Confirm), GameState::Menu);
+    assert_eq!(GameState::GameOver.handle_action(Action::Pause), GameState::GameOver);
+}
+
+#[test]
+fn only_playing_simulates() {
+    assert!(GameState::Playing.is_simulating());
+    assert!(!GameState::Menu.is_simulating());
+    assert!(!GameState::Paused.is_simulating());
+    assert!(!GameState::GameOver.is_simulating());
+}
@@ -0,0 +1,107 @@
+use ggez::mint::Point2;
+
+use shooter::entities::Shot;
+use shooter::time::*;
+
+fn distance_after_one_second(time_scale: f32) -> f32 {
+    let mut shot = Shot::new(Point2 { x: 0.0, y: 1000.0 }, Shot::direction_velocity(0.0));
+    for _ in 0..60 {
+        shot.update(scale_delta(1.0 / 60.0, time_scale));
+    }
+    1000.0 - shot.pos.y
+}
+
+#[test]
+fn half_time_scale_halves_distance() {
+    let normal = distance_after_one_second(1.0);
+    let slow = distance_after_one_second(0.5);
+
+    assert!((slow - normal / 2.0).abs() < 0.01);
+}
+
+#[test]
+fn negative_time_scale_freezes_time() {
+    assert_eq!(scale_delta(1.0 / 60.0, -1.0), 0.0);
+}
+
+#[test]
+fn long_stall_is_clamped() {
+    assert_eq!(clamp_delta(2.0), MAX_DELTA);
+}
+
+#[test]
+fn normal_frame_time_passes_through() {
+    assert_eq!(clamp_delta(1.0 / 60.0), 1.0 / 60.0);
+}
+
+#[test]
+fn long_stall_only_catches_up_a_few_ticks() {
+    assert_eq!(catch_up_ticks(2.0, 1.0 / 60.0), (MAX_DELTA * 60.0).round() as u32);
+}
+
+#[test]
+fn normal_frame_runs_one_tick() {
+    assert_eq!(catch_up_ticks(1.0 / 60.0, 1.0 / 60.0), 1);
+}
+
+#[test]
+fn hit_stop_freezes_for_exactly_its_duration() {
+    let mut hit_stop = HitStop::new();
+    assert!(!hit_stop.update(0.125));
+
+    hit_stop.trigger(0.375);
+    assert!(hit_stop.update(0.125));
+    assert!(hit_stop.update(0.125));
+    assert!(hit_stop.update(0.125));
+    assert!(!hit_stop.is_active());
+    assert!(!hit_stop.update(0.125));
+}
+
+#[test]
+fn shorter_hit_stop_keeps_longer_one() {
+    let mut hit_stop = HitStop::new();
+    hit_stop.trigger(0.5);
+    hit_stop.trigger(0.125);
+
+    hit_stop.update(0.25);
+    assert!(hit_stop.is_active());
+}
+
+#[test]
+fn paused_clock_keeps_real_time_only() {
+    let mut clock = GameClock::new();
+    clock.tick(0.03125, 1.0);
+    clock.tick(0.03125, 1.0);
+    assert_eq!(clock.elapsed(), 0.0625);
+    assert_eq!(clock.delta(), 0.03125);
+
+    clock.set_paused(true);
+    clock.tick(0.03125, 1.0);
+    clock.tick(0.03125, 1.0);
+    assert_eq!(clock.delta(), 0.0);
+    assert_eq!(clock.elapsed(), 0.0625);
+    assert_eq!(clock.real_delta(), 0.03125);
+    assert_eq!(clock.real_elapsed(), 0.125);
+
+    clock.set_paused(false);
+    clock.tick(0.03125, 1.0);
+    assert_eq!(clock.elapsed(), 0.09375);
+}
+
+#[test]
+fn clock_scales_game_time_only() {
+    let mut clock = GameClock::new();
+    clock.tick(0.03125, 0.5);
+    assert_eq!(clock.delta(), 0.015625);
+    assert_eq!(clock.real_delta(), 0.03125);
+}
+
+#[test]
+fn reset_clock_starts_game_time_over() {
+    let mut clock = GameClock::new();
+    clock.tick(0.03125, 1.0);
+    clock.reset();
+
+    assert_eq!(clock.elapsed(), 0.0);
+    assert_eq!(clock.real_elapsed(), 0.03125);
+}
@@ -0,0 +1,92 @@
+use ggez::mint::{Point2, Vector2};
+
+use shooter::entities::{Enemy, EnemyEvent};
+use shooter::timeline::*;
+
+mod common;
+use common::MockSprite;
+
+fn volley(count: usize) -> Action {
+    Action::FireVolley { count, spread: 0.25 }
+}
+
+#[test]
+fn actions_fire_at_their_scheduled_times() {
+    let mut timeline = Timeline::new(vec![
+        (1.0, volley(1)),
+        (0.5, Action::ChangePattern(PatternKind::SeekPlayer)),
+    ]);
+
+    assert!(timeline.advance(0.25).is_empty());
+    assert_eq!(timeline.advance(0.25), vec![Action::ChangePattern(PatternKind::SeekPlayer)]);
+    assert!(timeline.advance(0.25).is_empty());
+    assert_eq!(timeline.advance(0.25), vec![volley(1)]);
+    assert!(timeline.is_finished());
+    assert!(timeline.advance(10.0).is_empty());
+}
+
+#[test]
+fn looping_timeline_fires_each_action_once_per_pass() {
+    let mut timeline = Timeline::new(vec![
+        (0.25, volley(1)),
+        (0.75, volley(2)),
+    ]).with_loop(1.0);
+
+    let mut fired = Vec::new();
+    for tick in 0..16 {
+        for action in timeline.advance(0.125) {
+            fired.push((tick, action));
+        }
+    }
+
+    // Ticks are 0.125s long, so 0.25s in is the end of the second one
+    assert_eq!(fired, vec![
+        (1, volley(1)), (5, volley(2)),
+        (9, volley(1)), (13, volley(2)),
+    ]);
+    assert!(!timeline.is_finished());
+}
+
+#[test]
+fn long_update_goes_through_several_passes() {
+    let mut timeline = Timeline::new(vec![(0.5, volley(1))]).with_loop(1.0);
+
+    assert_eq!(timeline.advance(3.0), vec![volley(1); 3]);
+    assert_eq!(timeline.elapsed(), 0.0);
+}
+
+#[test]
+fn loop_is_never_shorter_than_the_last_action() {
+    let mut timeline = Timeline::new(vec![(2.0, volley(1))]).with_loop(0.5);
+
+    assert!(timeline.advance(1.5).is_empty());
+    assert_eq!(timeline.advance(0.5), vec![volley(1)]);
+    assert_eq!(timeline.advance(2.0), vec![volley(1)]);
+}
+
+#[test]
+fn empty_timeline_does_not_loop() {
+    let mut timeline = Timeline::new(Vec::new()).with_loop(0.0);
+
+    assert!(!timeline.is_looping());
+    assert!(timeline.advance(1.0).is_empty());
+}
+
+#[test]
+fn enemy_runs_its_timeline() {
+    let timeline = Timeline::new(vec![
+        (0.5, volley(3)),
+        (0.5, Action::Dash(Vector2 { x: 200.0, y: 0.0 })),
+    ]);
+    let mut enemy = Enemy::new("a", Point2 { x: 100.0, y: 0.0 }, 0.0, 1, 1, Box::new(MockSprite::default())).
+        unwrap().
+        with_timeline(timeline);
+
+    assert!(enemy.step(0.25, 800.0, 600.0).is_empty());
+
+    let events = enemy.step(0.25, 800.0, 600.0);
+    let shots = events.iter().filter(|event| matches!(event, EnemyEvent::FiredShot(_))).count();
+    assert_eq!(shots, 3);
+    assert!(enemy.velocity().x > 0.0);
+    assert!(enemy.timeline().unwrap().is_finished());
+}
@@ -0,0 +1,63 @@
+use ggez::graphics;
+use ggez::mint::{Point2, Vector2};
+
+use shooter::entities::{Player, Shot};
+use shooter::tuning::Tuning;
+
+#[test]
+fn no_tuning_means_defaults() {
+    assert_eq!(Tuning::parse(""), Ok(Tuning::default()));
+    assert_eq!(Tuning::parse("# nothing changed\n\n"), Ok(Tuning::default()));
+
+    let tuning = Tuning::default();
+    assert_eq!(tuning.player_speed, Player::SPEED);
+    assert_eq!(tuning.shot_timeout, Player::SHOT_TIMEOUT);
+    assert_eq!(tuning.shot_speed, Shot::SPEED);
+}
+
+#[test]
+fn tuning_overrides_only_what_it_mentions() {
+    let tuning = Tuning::parse("player_speed = 250  # slower\nshot_timeout=0.5").unwrap();
+
+    assert_eq!(tuning.player_speed, 250.0);
+    assert_eq!(tuning.shot_timeout, 0.5);
+    assert_eq!(tuning.shot_speed, Shot::SPEED);
+}
+
+#[test]
+fn bad_tuning_is_rejected() {
+    assert!(Tuning::parse("player_speed = -10").is_err());
+    assert!(Tuning::parse("shot_speed = fast").is_err());
+    assert!(Tuning::parse("jump_height = 3").is_err());
+    assert!(Tuning::parse("player_speed").is_err());
+}
+
+#[test]
+fn player_uses_its_tuning() {
+    let tuning = Tuning { player_speed: 100.0, shot_timeout: 0.25, shot_speed: 300.0 };
+    let mut player = Player::new(Point2 { x: 500.0, y: 500.0 }).with_tuning(tuning);
+    let play_area = graphics::Rect::new(0.0, 0.0, 1000.0, 1000.0);
+
+    for _ in 0..60 {
+        player.update(Vector2 { x: 1.0, y: 0.0 }, 1.0 / 60.0, play_area);
+    }
+    assert!((player.velocity.x - 100.0).abs() < 0.001);
+    assert_eq!(player.shot_timeout(), 0.25);
+
+    let shot = &player.spawn_shots()[0];
+    assert!((shot.velocity().y + 300.0).abs() < 0.001);
+}
+
+#[test]
+fn loaded_tuning_overrides_defaults() {
+    let tuning = Tuning::from_file(Some("shot_speed = 800"));
+
+    assert_eq!(tuning.shot_speed, 800.0);
+    assert_eq!(tuning.player_speed, Player::SPEED);
+}
+
+#[test]
+fn missing_or_broken_tuning_file_uses_defaults() {
+    assert_eq!(Tuning::from_file(None), Tuning::default());
+    assert_eq!(Tuning::from_file(Some("shot_speed = fast")), Tuning::default());
+}
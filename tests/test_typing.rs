@@ -0,0 +1,67 @@
+use ggez::mint::Point2;
+
+use shooter::entities::Enemy;
+use shooter::typing::*;
+
+mod common;
+use common::MockSprite;
+
+fn enemy(label: &str, y: f32) -> Enemy {
+    Enemy::new(label, Point2 { x: 0.0, y }, 100.0, 1, 1, Box::new(MockSprite::default())).unwrap()
+}
+
+fn type_word(typing: &mut TypingState, word: &str, enemies: &[Enemy]) -> Vec<Keystroke> {
+    word.chars().map(|character| typing.type_char(character, enemies)).collect()
+}
+
+#[test]
+fn typing_a_label_completes_it() {
+    let enemies = vec![enemy("Bloat", 0.0), enemy("NULLs", 0.0)];
+    let mut typing = TypingState::new();
+
+    let keystrokes = type_word(&mut typing, "bloa", &enemies);
+    assert!(keystrokes.iter().all(|keystroke| *keystroke == Keystroke::Progress { target: enemies[0].id() }));
+    assert_eq!(typing.buffer(), "bloa");
+    assert_eq!(typing.target(), Some(enemies[0].id()));
+
+    assert_eq!(typing.type_char('t', &enemies), Keystroke::Completed { target: enemies[0].id() });
+    assert_eq!(typing.buffer(), "");
+    assert_eq!(typing.target(), None);
+}
+
+#[test]
+fn wrong_character_is_a_miss() {
+    let enemies = vec![enemy("Bloat", 0.0)];
+    let mut typing = TypingState::new();
+
+    assert_eq!(typing.type_char('x', &enemies), Keystroke::Miss);
+    typing.type_char('b', &enemies);
+    assert_eq!(typing.type_char('x', &enemies), Keystroke::Miss);
+    assert_eq!(typing.buffer(), "b");
+}
+
+#[test]
+fn shared_prefix_locks_on_to_one_enemy() {
+    // Both start with "Un", the lower one gets picked and the other one can't be switched to
+    let enemies = vec![enemy("Undefined Behaviour", 100.0), enemy("Unnecessary Heap\nAllocations", 300.0)];
+    let mut typing = TypingState::new();
+
+    type_word(&mut typing, "un", &enemies);
+    assert_eq!(typing.target(), Some(enemies[1].id()));
+    assert_eq!(typing.type_char('d', &enemies), Keystroke::Miss);
+
+    let keystrokes = type_word(&mut typing, "necessary heap allocations", &enemies);
+    assert_eq!(keystrokes.last(), Some(&Keystroke::Completed { target: enemies[1].id() }));
+}
+
+#[test]
+fn target_destroyed_elsewhere_is_dropped() {
+    let mut enemies = vec![enemy("Bloat", 0.0), enemy("Segfaults", 0.0)];
+    let mut typing = TypingState::new();
+
+    type_word(&mut typing, "bl", &enemies);
+    enemies[0].is_alive = false;
+
+    assert_eq!(typing.type_char('s', &enemies), Keystroke::Progress { target: enemies[1].id() });
+    assert_eq!(typing.buffer(), "s");
+}
@@ -0,0 +1,76 @@
+use ggez::graphics::Rect;
+use ggez::mint::{Point2, Vector2};
+
+use shooter::entities::Player;
+use shooter::screen::ScreenConfig;
+use shooter::viewport::*;
+
+fn viewport(width: f32, height: f32) -> Viewport {
+    Viewport::new(ScreenConfig::new(1200.0, 1000.0), ScreenConfig::new(width, height))
+}
+
+#[test]
+fn wider_window_gets_bars_on_the_sides() {
+    let viewport = viewport(3000.0, 2000.0);
+
+    assert_eq!(viewport.scale(), 2.0);
+    let offset = viewport.offset();
+    assert_eq!((offset.x, offset.y), (300.0, 0.0));
+
+    let bars = viewport.bars();
+    assert_eq!(bars, [Rect::new(-150.0, 0.0, 150.0, 1000.0), Rect::new(1200.0, 0.0, 150.0, 1000.0)]);
+}
+
+#[test]
+fn taller_window_gets_bars_on_top_and_bottom() {
+    let viewport = viewport(600.0, 1000.0);
+
+    assert_eq!(viewport.scale(), 0.5);
+    let offset = viewport.offset();
+    assert_eq!((offset.x, offset.y), (0.0, 250.0));
+
+    let bars = viewport.bars();
+    assert_eq!(bars, [Rect::new(0.0, -500.0, 1200.0, 500.0), Rect::new(0.0, 1000.0, 1200.0, 500.0)]);
+}
+
+#[test]
+fn matching_window_has_no_bars() {
+    let viewport = viewport(1200.0, 1000.0);
+
+    assert_eq!(viewport.scale(), 1.0);
+    assert!(viewport.bars().is_empty());
+    assert_eq!(viewport.letterbox(Rect::new(0.0, 0.0, 1200.0, 1000.0)), Rect::new(0.0, 0.0, 1200.0, 1000.0));
+}
+
+#[test]
+fn window_points_map_back_to_virtual_space() {
+    let viewport = viewport(3000.0, 2000.0);
+
+    let point = viewport.to_virtual(Point2 { x: 300.0, y: 0.0 });
+    assert_eq!((point.x, point.y), (0.0, 0.0));
+
+    let point = viewport.to_virtual(Point2 { x: 1500.0, y: 1000.0 });
+    assert_eq!((point.x, point.y), (600.0, 500.0));
+}
+
+// The player is clamped to whatever the virtual width is at the time, so shrinking it pulls a
+// player who was against the old right edge back in straight away
+#[test]
+fn player_clamp_follows_each_virtual_resize() {
+    let mut viewport = viewport(1920.0, 1080.0);
+    let mut player = Player::new(Point2 { x: 600.0, y: 1000.0 });
+
+    for width in [800.0, 1600.0, 1000.0] {
+        viewport.virtual_size = ScreenConfig::new(width, 1000.0);
+        viewport.resize(width, 1000.0);
+        let play_area = Player::play_area(&viewport.virtual_size);
+
+        player.update(Vector2 { x: 0.0, y: 0.0 }, 1.0 / 60.0, play_area);
+        assert!(player.pos.x <= width);
+
+        for _ in 0..300 {
+            player.update(Vector2 { x: 1.0, y: 0.0 }, 1.0 / 60.0, play_area);
+        }
+        assert_eq!(player.pos.x, width);
+    }
+}
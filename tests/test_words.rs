@@ -0,0 +1,31 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use shooter::words::*;
+
+#[test]
+fn random_only_picks_listed_words() {
+    let words = WordList::parse("alpha\nbeta # comment\n\n# gamma\ndelta");
+    assert_eq!(words.words(), ["alpha", "beta", "delta"]);
+
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..100 {
+        let word = words.random(&mut rng);
+        assert!(words.words().iter().any(|listed| listed == word));
+    }
+}
+
+#[test]
+fn empty_list_falls_back_to_default_word() {
+    let words = WordList::parse("# nothing here\n");
+    let mut rng = StdRng::seed_from_u64(7);
+
+    assert_eq!(words.random(&mut rng), WordList::FALLBACK_WORD);
+}
+
+#[test]
+fn line_breaks_can_be_escaped() {
+    let words = WordList::parse("Unnecessary Heap\\nAllocations");
+
+    assert_eq!(words.words(), ["Unnecessary Heap\nAllocations"]);
+}